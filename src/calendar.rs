@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Sub},
+};
+
+/// Days in a calendar year.
+pub const DAYS_PER_YEAR: u64 = 365;
+
+/// Day in the calendar, counted from the first day of year 1.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+pub struct Date(pub u64);
+
+impl Date {
+    /// Year of the date, starting from 1.
+    pub fn year(self) -> u64 {
+        self.0 / DAYS_PER_YEAR + 1
+    }
+
+    /// Day within the year, starting from 1.
+    pub fn day_of_year(self) -> u64 {
+        self.0 % DAYS_PER_YEAR + 1
+    }
+
+    /// Time left until the given date, zero if it has passed.
+    pub fn until(self, later: Date) -> Duration {
+        Duration(later.0.saturating_sub(self.0))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Year {}, day {}", self.year(), self.day_of_year())
+    }
+}
+
+impl Add<Duration> for Date {
+    type Output = Date;
+
+    fn add(self, duration: Duration) -> Date {
+        Date(self.0 + duration.0)
+    }
+}
+
+impl AddAssign<Duration> for Date {
+    fn add_assign(&mut self, duration: Duration) {
+        self.0 += duration.0;
+    }
+}
+
+impl Sub for Date {
+    type Output = Duration;
+
+    fn sub(self, earlier: Date) -> Duration {
+        earlier.until(self)
+    }
+}
+
+/// Length of time in whole days.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+pub struct Duration(pub u64);
+
+impl Duration {
+    /// Duration of the given number of years.
+    pub fn years(years: u64) -> Self {
+        Duration(years * DAYS_PER_YEAR)
+    }
+
+    /// Number of days in the duration.
+    pub fn days(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = |count: u64| if count == 1 { "" } else { "s" };
+        let (years, days) = (self.0 / DAYS_PER_YEAR, self.0 % DAYS_PER_YEAR);
+        match (years, days) {
+            (0, days) => write!(f, "{} day{}", days, plural(days)),
+            (years, 0) => write!(f, "{} year{}", years, plural(years)),
+            (years, days) => write!(
+                f,
+                "{} year{}, {} day{}",
+                years,
+                plural(years),
+                days,
+                plural(days)
+            ),
+        }
+    }
+}
+
+/// Rate at which game time passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    Paused,
+    Normal,
+    Double,
+    Fast,
+    /// As many days as can be simulated without stalling the UI.
+    Max,
+}
+
+impl Speed {
+    /// Multiple of the normal speed, none when running as fast as possible.
+    pub fn factor(self) -> Option<f64> {
+        match self {
+            Speed::Paused => Some(0.),
+            Speed::Normal => Some(1.),
+            Speed::Double => Some(2.),
+            Speed::Fast => Some(5.),
+            Speed::Max => None,
+        }
+    }
+
+    /// Human readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Speed::Paused => "paused",
+            Speed::Normal => "1x",
+            Speed::Double => "2x",
+            Speed::Fast => "5x",
+            Speed::Max => "max",
+        }
+    }
+}
+
+/// Game clock, turning real time into game time at the chosen speed.
+#[derive(Debug, Clone)]
+pub struct Clock {
+    speed: Speed,
+    /// Speed to resume at once unpaused.
+    resume: Speed,
+    /// Fraction of a day passed since the last whole day.
+    elapsed: f64,
+}
+
+impl Clock {
+    /// In-game days passing per second of real time at normal speed.
+    const DAYS_PER_SECOND: f64 = 2.;
+
+    /// Most real time in seconds advanced at once, so that a stalled frame
+    /// does not skip ahead.
+    const MAX_STEP: f64 = 0.25;
+
+    /// Create a new clock running at normal speed.
+    pub fn new() -> Self {
+        Clock {
+            speed: Speed::Normal,
+            resume: Speed::Normal,
+            elapsed: 0.,
+        }
+    }
+
+    /// Current speed.
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    /// Run at the given speed.
+    pub fn set_speed(&mut self, speed: Speed) {
+        if speed != Speed::Paused {
+            self.resume = speed;
+        }
+        self.speed = speed;
+    }
+
+    /// Pause the clock, or resume at the speed it ran at before.
+    pub fn toggle_pause(&mut self) {
+        if self.speed == Speed::Paused {
+            self.speed = self.resume;
+        } else {
+            self.speed = Speed::Paused;
+        }
+    }
+
+    /// Advance the clock by the given seconds of real time.
+    /// Returns the in-game days passed, or none at max speed where the
+    /// caller decides how many whole days to run.
+    pub fn advance(&mut self, seconds: f64) -> Option<f64> {
+        let days = seconds.min(Self::MAX_STEP) * Self::DAYS_PER_SECOND * self.speed.factor()?;
+        self.elapsed += days;
+        Some(days)
+    }
+
+    /// Take one whole day passed since the last, returning whether there was one.
+    pub fn tick(&mut self) -> bool {
+        if self.elapsed >= 1. {
+            self.elapsed -= 1.;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar() {
+        let date = Date(400);
+        assert_eq!(date.to_string(), "Year 2, day 36");
+        assert_eq!(date + Duration(30) - date, Duration(30));
+        assert_eq!(date.until(Date(0)), Duration(0));
+        assert_eq!(Duration(1).to_string(), "1 day");
+        assert_eq!(Duration::years(2).to_string(), "2 years");
+        assert_eq!(Duration(400).to_string(), "1 year, 35 days");
+    }
+
+    #[test]
+    fn test_clock() {
+        let mut clock = Clock::new();
+        assert_eq!(clock.advance(0.25), Some(0.5));
+        assert!(!clock.tick());
+        clock.set_speed(Speed::Fast);
+        assert_eq!(clock.advance(10.), Some(2.5));
+        assert!(clock.tick() && clock.tick() && clock.tick() && !clock.tick());
+
+        clock.toggle_pause();
+        assert_eq!(clock.advance(0.1), Some(0.));
+        clock.toggle_pause();
+        assert_eq!(clock.speed(), Speed::Fast);
+        clock.set_speed(Speed::Max);
+        assert_eq!(clock.advance(0.1), None);
+    }
+}
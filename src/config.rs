@@ -1,9 +1,20 @@
 use serde::Deserialize;
+use std::num::NonZeroU64;
 
 /// Application level configuration options.
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub simulation: Simulation,
+    #[serde(default)]
+    pub generation: Generation,
+    #[serde(default)]
+    pub mining: Mining,
+    #[serde(default)]
+    pub saves: Saves,
+    #[serde(default)]
+    pub controls: Controls,
+    #[serde(default)]
+    pub gui: Gui,
 }
 
 /// Parameters used for simulation and generation.
@@ -12,4 +23,174 @@ pub struct Simulation {
     pub map_seed: u32,
     pub number_of_systems: u64,
     pub system_spread: f64,
+    pub lane_length: f64,
+    pub lanes_per_system: usize,
+    pub settled_fraction: f64,
+    pub years: u64,
+    /// Days advanced on each simulation step, at least one.
+    pub step_days: NonZeroU64,
+    pub population_growth: f64,
+    pub population_capacity: f64,
+    pub number_of_factions: usize,
+    /// Minimum distance between the home systems of factions.
+    pub faction_spacing: f64,
+    /// Systems claimed by each faction per year.
+    pub expansion_rate: f64,
+}
+
+/// World generation pipeline.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Generation {
+    /// Names of the generation stages to run, in order.
+    pub stages: Vec<String>,
+}
+
+impl Default for Generation {
+    fn default() -> Self {
+        Generation {
+            stages: [
+                "placement",
+                "stars",
+                "planets",
+                "deposits",
+                "lanes",
+                "settlement",
+                "factions",
+                "naming",
+                "history",
+                "player",
+                "charts",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        }
+    }
+}
+
+/// Yield and depletion curves of mining operations.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Mining {
+    /// Units extracted per day by an outpost from a full deposit.
+    pub outpost_yield: f64,
+    /// Units extracted per day by a harvester from a full deposit.
+    pub harvester_yield: f64,
+    /// Yield falls with the remaining fraction of a deposit raised to this power.
+    pub depletion_exponent: f64,
+    /// Remaining fraction of a deposit below which it is exhausted.
+    pub exhausted_fraction: f64,
+    /// Units an operation stores before it has to be emptied.
+    pub storage: f64,
+}
+
+impl Default for Mining {
+    fn default() -> Self {
+        Mining {
+            outpost_yield: 20.,
+            harvester_yield: 30.,
+            depletion_exponent: 1.5,
+            exhausted_fraction: 0.02,
+            storage: 1_000.,
+        }
+    }
+}
+
+/// Where and how often the game is saved.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Saves {
+    /// Directory holding one file per save slot.
+    pub directory: String,
+    /// In-game days between autosaves, 0 disables autosaving.
+    pub autosave_days: u64,
+    /// Slot overwritten by each autosave.
+    pub autosave_slot: String,
+}
+
+impl Default for Saves {
+    fn default() -> Self {
+        Saves {
+            directory: "saves".to_string(),
+            autosave_days: 30,
+            autosave_slot: "autosave".to_string(),
+        }
+    }
+}
+
+/// User interface options.
+#[derive(Debug, Default, Deserialize)]
+pub struct Gui {
+    /// File to record all UI events to, for later replay.
+    pub record_events: Option<String>,
+    /// File with recorded UI events to replay on startup.
+    pub replay_events: Option<String>,
+}
+
+/// Input bindings for each UI action.
+/// Keys use SDL key names, optionally prefixed by `Ctrl+`, `Shift+` or `Alt+`.
+/// Mouse input is bound using `Mouse Left`, `Mouse Middle`, `Mouse Right`,
+/// `Wheel Up` and `Wheel Down`.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Controls {
+    pub pan_left: Vec<String>,
+    pub pan_right: Vec<String>,
+    pub pan_up: Vec<String>,
+    pub pan_down: Vec<String>,
+    pub zoom_in: Vec<String>,
+    pub zoom_out: Vec<String>,
+    pub select: Vec<String>,
+    pub back: Vec<String>,
+    pub toggle_overlay: Vec<String>,
+    pub search: Vec<String>,
+    pub history: Vec<String>,
+    pub details: Vec<String>,
+    pub survey: Vec<String>,
+    pub claim: Vec<String>,
+    pub sell: Vec<String>,
+    pub build: Vec<String>,
+    pub deliver: Vec<String>,
+    pub outfit: Vec<String>,
+    pub save: Vec<String>,
+    pub load: Vec<String>,
+    pub pause: Vec<String>,
+    pub normal_speed: Vec<String>,
+    pub double_speed: Vec<String>,
+    pub fast_speed: Vec<String>,
+    pub max_speed: Vec<String>,
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        let keys = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Controls {
+            pan_left: keys(&["Left", "A"]),
+            pan_right: keys(&["Right", "D"]),
+            pan_up: keys(&["Up", "W"]),
+            pan_down: keys(&["Down", "S"]),
+            zoom_in: keys(&["Wheel Up", "=", "Shift+="]),
+            zoom_out: keys(&["Wheel Down", "-"]),
+            select: keys(&["Mouse Left", "Return"]),
+            back: keys(&["Escape"]),
+            toggle_overlay: keys(&["Tab"]),
+            search: keys(&["Ctrl+F", "/"]),
+            history: keys(&["H"]),
+            details: keys(&["Mouse Right", "I"]),
+            survey: keys(&["V"]),
+            claim: keys(&["C"]),
+            sell: keys(&["X"]),
+            build: keys(&["B"]),
+            deliver: keys(&["L"]),
+            outfit: keys(&["O"]),
+            save: keys(&["F5"]),
+            load: keys(&["F9"]),
+            pause: keys(&["Space"]),
+            normal_speed: keys(&["1"]),
+            double_speed: keys(&["2"]),
+            fast_speed: keys(&["3"]),
+            max_speed: keys(&["4"]),
+        }
+    }
 }
@@ -0,0 +1,247 @@
+use crate::calendar::DAYS_PER_YEAR;
+use crate::entity::astronomical::{Body, BodyKind, LaneIndex, StarClass, SystemIndex};
+use serde::{Deserialize, Serialize};
+
+/// Tradeable goods.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Commodity {
+    Ore,
+    Fuel,
+    Food,
+    Tech,
+}
+
+impl Commodity {
+    /// All commodities, in market order.
+    pub const ALL: [Commodity; 4] = [
+        Commodity::Ore,
+        Commodity::Fuel,
+        Commodity::Food,
+        Commodity::Tech,
+    ];
+
+    /// Price per unit when stock matches demand.
+    pub fn base_price(self) -> f64 {
+        match self {
+            Commodity::Ore => 20.,
+            Commodity::Fuel => 35.,
+            Commodity::Food => 10.,
+            Commodity::Tech => 120.,
+        }
+    }
+
+    /// Human readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Commodity::Ore => "Ore",
+            Commodity::Fuel => "Fuel",
+            Commodity::Food => "Food",
+            Commodity::Tech => "Tech",
+        }
+    }
+}
+
+/// Units of each commodity held, e.g. in the hold of a ship.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Cargo {
+    units: Vec<f64>,
+}
+
+impl Cargo {
+    /// Units of the given commodity held.
+    pub fn get(&self, commodity: Commodity) -> f64 {
+        self.units.get(commodity as usize).cloned().unwrap_or(0.)
+    }
+
+    /// Add units of the given commodity.
+    pub fn add(&mut self, commodity: Commodity, units: f64) {
+        if self.units.is_empty() {
+            self.units = vec![0.; Commodity::ALL.len()];
+        }
+        self.units[commodity as usize] += units;
+    }
+
+    /// Remove up to the given units of the commodity, returning the units removed.
+    pub fn take(&mut self, commodity: Commodity, units: f64) -> f64 {
+        let taken = self.get(commodity).min(units);
+        if taken > 0. {
+            self.units[commodity as usize] -= taken;
+        }
+        taken
+    }
+
+    /// Total units of all commodities held.
+    pub fn total(&self) -> f64 {
+        self.units.iter().sum()
+    }
+
+    /// Each commodity held, along with its units.
+    pub fn goods(&self) -> impl Iterator<Item = (Commodity, f64)> + '_ {
+        Commodity::ALL
+            .iter()
+            .map(move |&commodity| (commodity, self.get(commodity)))
+            .filter(|&(_, units)| units > 0.)
+    }
+}
+
+/// State of a single commodity in a market.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Good {
+    /// Units currently in stock.
+    pub stock: f64,
+    /// Units produced per year.
+    pub production: f64,
+    /// Units consumed per year.
+    pub consumption: f64,
+    /// Current price per unit.
+    pub price: f64,
+}
+
+/// Market of a single system, holding the state of each commodity.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Market {
+    goods: Vec<Good>,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Market {
+            goods: Commodity::ALL
+                .iter()
+                .map(|commodity| Good {
+                    stock: 0.,
+                    production: 0.,
+                    consumption: 0.,
+                    price: commodity.base_price(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Market {
+    /// Years of consumption the market tries to keep in stock.
+    const RESERVE: f64 = 0.25;
+    /// How strongly prices react to shortage or surplus.
+    const ELASTICITY: f64 = 0.5;
+    /// Price limits relative to the base price.
+    const MIN_PRICE: f64 = 0.2;
+    const MAX_PRICE: f64 = 5.;
+    /// Days for prices to catch up with changes in stock.
+    const ADJUSTMENT_DAYS: f64 = 30.;
+
+    /// State of the given commodity.
+    pub fn good(&self, commodity: Commodity) -> &Good {
+        &self.goods[commodity as usize]
+    }
+
+    /// Mutable state of the given commodity.
+    pub fn good_mut(&mut self, commodity: Commodity) -> &mut Good {
+        &mut self.goods[commodity as usize]
+    }
+
+    /// Current price of the given commodity.
+    pub fn price(&self, commodity: Commodity) -> f64 {
+        self.good(commodity).price
+    }
+
+    /// Units of the commodity the market wants in stock.
+    pub fn demand(&self, commodity: Commodity) -> f64 {
+        self.good(commodity).consumption * Market::RESERVE
+    }
+
+    /// Sell units of the commodity to the market at the current price,
+    /// returning the credits paid.
+    pub fn sell(&mut self, commodity: Commodity, units: f64) -> f64 {
+        let good = self.good_mut(commodity);
+        good.stock += units;
+        units * good.price
+    }
+
+    /// Set the yearly production and consumption of each commodity from
+    /// the population, star and bodies of the system.
+    pub fn set_rates(&mut self, population: u64, star: StarClass, bodies: &[&Body]) {
+        let count = |kind| bodies.iter().filter(|body| body.kind == kind).count() as f64;
+        // Rates are per thousand inhabitants.
+        let population = population as f64 / 1_000.;
+
+        let habitable = (count(BodyKind::Ocean) + 0.3 * count(BodyKind::Rocky)).min(2.);
+        let minable = (count(BodyKind::Belt) + 0.4 * count(BodyKind::Rocky)).min(3.);
+        // Gas giants can be skimmed, bright stars scooped.
+        let fuel = (count(BodyKind::GasGiant) + star.luminosity().min(10.) / 5.).min(3.);
+
+        let tech = population * 0.15;
+        let rates = [
+            (population * 0.5 * minable, population * 0.1 + tech * 0.5),
+            (population * 0.3 * fuel, population * 0.2),
+            (population * 0.8 * habitable, population),
+            (tech, population * 0.1),
+        ];
+        for (good, (production, consumption)) in self.goods.iter_mut().zip(rates.iter()) {
+            good.production = *production;
+            good.consumption = *consumption;
+        }
+    }
+
+    /// Advance the market the given number of days, producing and consuming
+    /// goods and moving prices towards what the stock supports.
+    pub fn update(&mut self, days: u64) {
+        let years = days as f64 / DAYS_PER_YEAR as f64;
+        let adjustment = (days as f64 / Market::ADJUSTMENT_DAYS).min(1.);
+        for (good, commodity) in self.goods.iter_mut().zip(Commodity::ALL.iter()) {
+            good.stock = (good.stock + (good.production - good.consumption) * years).max(0.);
+
+            let demand = good.consumption * Market::RESERVE;
+            let target = commodity.base_price()
+                * ((demand + 1.) / (good.stock + 1.))
+                    .powf(Market::ELASTICITY)
+                    .clamp(Market::MIN_PRICE, Market::MAX_PRICE);
+            good.price += (target - good.price) * adjustment;
+        }
+    }
+}
+
+/// Goods carried by traders along lanes to another market.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Shipment {
+    pub commodity: Commodity,
+    pub units: f64,
+    pub destination: SystemIndex,
+    /// Lanes to travel, in order.
+    pub route: Vec<LaneIndex>,
+    /// Number of lanes of the route already travelled.
+    pub leg: usize,
+    /// Distance travelled along the current lane.
+    pub progress: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_market_prices() {
+        let ocean = Body::builder()
+            .system(SystemIndex::from(0))
+            .kind(BodyKind::Ocean)
+            .orbit(1.)
+            .build();
+        let mut market = Market::default();
+        market.set_rates(1_000_000, StarClass::G, &[&ocean, &ocean]);
+
+        // Plenty of food but no ore, fuel or tech production.
+        for _ in 0..24 {
+            market.update(30);
+        }
+        assert!(market.price(Commodity::Food) < Commodity::Food.base_price());
+        assert!(market.price(Commodity::Ore) > Commodity::Ore.base_price());
+        assert!(market.price(Commodity::Tech) < Commodity::Tech.base_price());
+        assert_eq!(market.good(Commodity::Ore).stock, 0.);
+
+        // Prices never leave the allowed range.
+        for commodity in Commodity::ALL.iter() {
+            let relative = market.price(*commodity) / commodity.base_price();
+            assert!((Market::MIN_PRICE..=Market::MAX_PRICE).contains(&relative));
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+use crate::prospecting::Deposit;
+
+/// Kind of body orbiting a star.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BodyKind {
+    Rocky,
+    Ocean,
+    GasGiant,
+    Ice,
+    Belt,
+}
+
+impl BodyKind {
+    /// Human readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            BodyKind::Rocky => "Rocky planet",
+            BodyKind::Ocean => "Ocean world",
+            BodyKind::GasGiant => "Gas giant",
+            BodyKind::Ice => "Ice world",
+            BodyKind::Belt => "Asteroid belt",
+        }
+    }
+}
+
+/// Planet or asteroid belt orbiting the star of a system.
+#[derive(Serialize, Deserialize, Debug, TypedBuilder, Clone, PartialEq)]
+#[builder(field(public))]
+pub struct Body {
+    pub system: SystemIndex,
+    pub kind: BodyKind,
+    /// Orbital radius in astronomical units.
+    pub orbit: f64,
+    /// Resources within the body, hidden until surveyed.
+    #[builder(default)]
+    pub deposits: Vec<Deposit>,
+}
+
+impl Entity for Body {
+    type Index = BodyIndex;
+}
+
+/// Index type for Body
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BodyIndex(usize);
+
+impl Into<usize> for BodyIndex {
+    fn into(self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for BodyIndex {
+    fn from(index: usize) -> BodyIndex {
+        BodyIndex(index)
+    }
+}
+
+impl EntityIndex for BodyIndex {}
@@ -0,0 +1,57 @@
+use super::*;
+
+/// Hyperlane connecting two star systems.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Lane {
+    pub from: SystemIndex,
+    pub to: SystemIndex,
+    pub length: f64,
+    /// Total units of goods carried along the lane by traders.
+    #[serde(default)]
+    pub volume: f64,
+}
+
+impl Lane {
+    /// Create a new lane between the given systems.
+    pub fn new(from: SystemIndex, to: SystemIndex, length: f64) -> Self {
+        Lane {
+            from,
+            to,
+            length,
+            volume: 0.,
+        }
+    }
+
+    /// Returns the system at the other end of the lane, if it is connected to the given one.
+    pub fn other(&self, system: SystemIndex) -> Option<SystemIndex> {
+        if self.from == system {
+            Some(self.to)
+        } else if self.to == system {
+            Some(self.from)
+        } else {
+            None
+        }
+    }
+}
+
+impl Entity for Lane {
+    type Index = LaneIndex;
+}
+
+/// Index type for Lane
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LaneIndex(usize);
+
+impl Into<usize> for LaneIndex {
+    fn into(self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for LaneIndex {
+    fn from(index: usize) -> LaneIndex {
+        LaneIndex(index)
+    }
+}
+
+impl EntityIndex for LaneIndex {}
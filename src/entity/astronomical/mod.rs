@@ -2,5 +2,11 @@ use super::*;
 use serde::{Deserialize, Serialize};
 use typed_builder::*;
 
+pub mod body;
+pub mod lane;
+pub mod star;
 pub mod system;
-pub use system::System;
+pub use body::{Body, BodyIndex, BodyKind};
+pub use lane::{Lane, LaneIndex};
+pub use star::StarClass;
+pub use system::{System, SystemIndex};
@@ -0,0 +1,58 @@
+use super::*;
+
+/// Spectral class of a main sequence star, from hottest to coolest.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StarClass {
+    O,
+    B,
+    A,
+    F,
+    G,
+    K,
+    M,
+}
+
+impl StarClass {
+    /// All classes, from hottest to coolest.
+    pub const ALL: [StarClass; 7] = [
+        StarClass::O,
+        StarClass::B,
+        StarClass::A,
+        StarClass::F,
+        StarClass::G,
+        StarClass::K,
+        StarClass::M,
+    ];
+
+    /// Fraction of main sequence stars belonging to the class.
+    pub fn frequency(self) -> f64 {
+        match self {
+            StarClass::O => 0.000_03,
+            StarClass::B => 0.001_3,
+            StarClass::A => 0.006,
+            StarClass::F => 0.03,
+            StarClass::G => 0.076,
+            StarClass::K => 0.121,
+            StarClass::M => 0.765_67,
+        }
+    }
+
+    /// Luminosity relative to the sun, typical for the class.
+    pub fn luminosity(self) -> f64 {
+        match self {
+            StarClass::O => 30_000.,
+            StarClass::B => 1_000.,
+            StarClass::A => 20.,
+            StarClass::F => 3.,
+            StarClass::G => 1.,
+            StarClass::K => 0.3,
+            StarClass::M => 0.04,
+        }
+    }
+}
+
+impl Default for StarClass {
+    fn default() -> Self {
+        StarClass::M
+    }
+}
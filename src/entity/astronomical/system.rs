@@ -1,5 +1,5 @@
 use super::*;
-use crate::point::Point;
+use crate::{economy::Market, entity::faction::FactionIndex, point::Point};
 use std::hash::{Hash, Hasher};
 
 #[derive(Serialize, Deserialize, Debug, TypedBuilder, Clone)]
@@ -8,6 +8,31 @@ use std::hash::{Hash, Hasher};
 pub struct System {
     pub location: Point,
     pub name: String,
+    #[builder(default)]
+    pub star: StarClass,
+    #[builder(default)]
+    pub bodies: Vec<BodyIndex>,
+    #[builder(default)]
+    pub population: u64,
+    #[builder(default)]
+    pub market: Market,
+    #[builder(default)]
+    pub faction: Option<FactionIndex>,
+    /// Whether the system lies on the front of a war its faction is fighting.
+    #[builder(default)]
+    pub contested: bool,
+}
+
+impl System {
+    /// Whether ships can refuel here, at the station of an inhabited system
+    /// or by skimming a gas giant.
+    pub fn refuels(&self, bodies: &EntityArray<Body>) -> bool {
+        self.population > 0
+            || self
+                .bodies
+                .iter()
+                .any(|&body| bodies[body].kind == BodyKind::GasGiant)
+    }
 }
 
 impl Hash for System {
@@ -29,6 +54,7 @@ impl Entity for System {
 }
 
 /// Index type for System
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SystemIndex(usize);
 
 impl Into<usize> for SystemIndex {
@@ -0,0 +1,163 @@
+use super::*;
+use crate::{calendar::Date, entity::astronomical::SystemIndex};
+use serde::{Deserialize, Serialize};
+use typed_builder::*;
+
+/// Form of government of a faction, affecting its behaviour.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Government {
+    Democracy,
+    Monarchy,
+    Corporate,
+    Theocracy,
+    Military,
+}
+
+impl Government {
+    /// All forms of government.
+    pub const ALL: [Government; 5] = [
+        Government::Democracy,
+        Government::Monarchy,
+        Government::Corporate,
+        Government::Theocracy,
+        Government::Military,
+    ];
+
+    /// Title used when naming factions with this government.
+    pub fn title(self) -> &'static str {
+        match self {
+            Government::Democracy => "Republic",
+            Government::Monarchy => "Kingdom",
+            Government::Corporate => "Combine",
+            Government::Theocracy => "Covenant",
+            Government::Military => "Directorate",
+        }
+    }
+
+    /// How eager factions with this government are to expand, relative to others.
+    pub fn aggression(self) -> f64 {
+        match self {
+            Government::Democracy => 0.8,
+            Government::Monarchy => 1.,
+            Government::Corporate => 1.2,
+            Government::Theocracy => 0.9,
+            Government::Military => 1.5,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, TypedBuilder, Clone)]
+#[builder(field(public))]
+/// Political power controlling a number of systems.
+pub struct Faction {
+    pub name: String,
+    /// Color used for the faction on the map, as RGB.
+    pub color: (u8, u8, u8),
+    pub government: Government,
+    pub home: SystemIndex,
+    #[builder(default)]
+    pub systems: Vec<SystemIndex>,
+}
+
+impl Entity for Faction {
+    type Index = FactionIndex;
+}
+
+/// Index type for Faction
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FactionIndex(usize);
+
+impl Into<usize> for FactionIndex {
+    fn into(self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for FactionIndex {
+    fn from(index: usize) -> FactionIndex {
+        FactionIndex(index)
+    }
+}
+
+impl EntityIndex for FactionIndex {}
+
+/// Treaty between two factions.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum Treaty {
+    #[default]
+    None,
+    /// Neither side may declare war until the given day.
+    NonAggression { until: Date },
+    /// Both sides join wars against either.
+    Alliance,
+}
+
+/// Ongoing war between two factions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct War {
+    pub aggressor: FactionIndex,
+    /// Day the war was declared.
+    pub since: Date,
+}
+
+/// Diplomatic state between two factions.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Relation {
+    /// Mutual opinion, from -100 (hatred) to 100 (friendship).
+    pub opinion: f64,
+    pub treaty: Treaty,
+    pub war: Option<War>,
+}
+
+/// Relations between every pair of factions.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Relations {
+    count: usize,
+    relations: Vec<Relation>,
+}
+
+impl Relations {
+    /// Create neutral relations between the given number of factions.
+    pub fn new(count: usize) -> Self {
+        Relations {
+            count,
+            relations: vec![Relation::default(); count * count],
+        }
+    }
+
+    /// Number of factions covered.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether no factions are covered.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn index(&self, a: FactionIndex, b: FactionIndex) -> usize {
+        let (a, b): (usize, usize) = (a.into(), b.into());
+        a.min(b) * self.count + a.max(b)
+    }
+
+    /// Relation between the given factions, in either order.
+    pub fn get(&self, a: FactionIndex, b: FactionIndex) -> &Relation {
+        &self.relations[self.index(a, b)]
+    }
+
+    /// Mutable relation between the given factions, in either order.
+    pub fn get_mut(&mut self, a: FactionIndex, b: FactionIndex) -> &mut Relation {
+        let index = self.index(a, b);
+        &mut self.relations[index]
+    }
+
+    /// Whether the given factions are at war.
+    pub fn at_war(&self, a: FactionIndex, b: FactionIndex) -> bool {
+        self.get(a, b).war.is_some()
+    }
+
+    /// Whether the given factions are allied.
+    pub fn allied(&self, a: FactionIndex, b: FactionIndex) -> bool {
+        self.get(a, b).treaty == Treaty::Alliance
+    }
+}
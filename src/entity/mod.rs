@@ -1,4 +1,6 @@
 pub mod astronomical;
+pub mod faction;
+pub mod ship;
 
 use serde::{Deserialize, Serialize};
 use std::ops::{Index, IndexMut};
@@ -18,7 +20,7 @@ where
 
 /// Generic array over one type of entity.
 /// Indexable using that the entity index type.
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct EntityArray<T>(Vec<T>);
 
 impl<T> EntityArray<T>
@@ -56,6 +58,22 @@ where
     pub fn get_mut(&mut self, index: T::Index) -> Option<&mut T> {
         self.0.get_mut(index.into())
     }
+
+    /// Returns an iterator over all entities.
+    pub fn iter(&self) -> ::std::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Returns an iterator allowing modification of all entities.
+    pub fn iter_mut(&mut self) -> ::std::slice::IterMut<'_, T> {
+        self.0.iter_mut()
+    }
+}
+
+impl<T: Entity> Default for EntityArray<T> {
+    fn default() -> Self {
+        EntityArray::new()
+    }
 }
 
 impl<T: Entity> IntoIterator for EntityArray<T> {
@@ -0,0 +1,383 @@
+use super::*;
+use crate::{
+    economy::Cargo,
+    entity::{
+        astronomical::{Lane, LaneIndex, System, SystemIndex},
+        faction::FactionIndex,
+    },
+    knowledge::Observer,
+    outfitting::Outfit,
+    point::Point,
+};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use typed_builder::*;
+
+#[derive(Serialize, Deserialize, Debug, TypedBuilder, Clone)]
+#[builder(field(public))]
+/// Spacecraft travelling between systems, along lanes or by jumping directly.
+pub struct Ship {
+    pub name: String,
+    /// Current location, between systems while travelling.
+    pub location: Point,
+    /// System the ship is at, or most recently left while travelling.
+    pub system: SystemIndex,
+    /// Final system of the current route, if travelling.
+    #[builder(default)]
+    pub destination: Option<SystemIndex>,
+    /// Remaining legs of the route, the one currently travelled first.
+    #[builder(default)]
+    pub route: Vec<Leg>,
+    /// Distance travelled along the current leg.
+    #[builder(default)]
+    pub progress: f64,
+    /// Distance travelled per day.
+    pub speed: f64,
+    pub fuel: f64,
+    /// Most fuel the ship can hold, filled up when refuelling.
+    #[builder(default)]
+    pub fuel_capacity: f64,
+    /// Longest direct jump between systems not connected by a lane.
+    #[builder(default)]
+    pub jump_range: f64,
+    /// Range of the sensors, within which systems are detected.
+    #[builder(default)]
+    pub sensors: f64,
+    /// Quality of the survey scanner, from 0 to 1.
+    #[builder(default)]
+    pub scanner: f64,
+    /// Goods carried.
+    #[builder(default)]
+    pub cargo: Cargo,
+    /// Units of goods which can be carried.
+    #[builder(default)]
+    pub capacity: f64,
+    /// Rating of the weapons, to attack with.
+    #[builder(default)]
+    pub weapons: f64,
+    /// Rating of the shields, to defend with.
+    #[builder(default)]
+    pub shields: f64,
+    /// Hull and modules the stats derive from, if the ship can be refitted.
+    #[builder(default)]
+    pub outfit: Option<Outfit>,
+    /// Whose knowledge of the galaxy the ship contributes to, if anyone's.
+    #[builder(default)]
+    pub observer: Option<Observer>,
+    /// Faction owning the ship, if any.
+    #[builder(default)]
+    pub faction: Option<FactionIndex>,
+    /// Duty of the ship, if flown by a faction.
+    #[builder(default)]
+    pub role: Option<Role>,
+}
+
+/// Duty of a ship flown by a faction.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Flying between inhabited systems.
+    Trader,
+    /// Guarding the systems of its faction.
+    Patrol,
+    /// Raiding dangerous space.
+    Pirate,
+}
+
+impl Role {
+    /// Human readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Trader => "Trader",
+            Role::Patrol => "Patrol",
+            Role::Pirate => "Pirate",
+        }
+    }
+}
+
+impl Ship {
+    /// Fuel used per unit of distance travelled along lanes.
+    pub const FUEL_PER_DISTANCE: f64 = 0.05;
+
+    /// Fuel used per unit of distance jumped without a lane.
+    pub const JUMP_FUEL_PER_DISTANCE: f64 = 0.1;
+
+    /// Fit the ship with the outfit, deriving its stats from it.
+    /// Fuel beyond the capacity of the new tanks is lost.
+    pub fn refit(&mut self, outfit: Outfit) {
+        let stats = outfit.stats();
+        self.speed = stats.speed;
+        self.jump_range = stats.jump_range;
+        self.fuel_capacity = stats.fuel_capacity;
+        self.fuel = self.fuel.min(stats.fuel_capacity);
+        self.capacity = stats.capacity;
+        self.sensors = stats.sensors;
+        self.scanner = stats.scanner;
+        self.weapons = stats.weapons;
+        self.shields = stats.shields;
+        self.outfit = Some(outfit);
+    }
+
+    /// Whether the ship is currently travelling.
+    pub fn travelling(&self) -> bool {
+        !self.route.is_empty()
+    }
+
+    /// System new routes start from, the end of the current leg if part
+    /// way along it.
+    pub fn departure(&self, lanes: &EntityArray<Lane>) -> SystemIndex {
+        match self.route.first() {
+            Some(leg) if self.progress > 0. => leg.end(self.system, lanes),
+            _ => self.system,
+        }
+    }
+
+    /// Order the ship to travel the given legs, starting from its departure system.
+    /// A ship already travelling first completes the leg it is on.
+    pub fn order(&mut self, route: Vec<Leg>, destination: SystemIndex) {
+        let current = if self.progress > 0. {
+            self.route.first().cloned()
+        } else {
+            None
+        };
+        self.route = current.into_iter().chain(route).collect();
+        self.destination = Some(destination).filter(|_| !self.route.is_empty());
+    }
+
+    /// Advance the ship along its route for the given number of days,
+    /// refuelling while docked at or arriving at systems for which `refuels` holds.
+    /// Returns the systems arrived at, in order.
+    pub fn advance<F>(
+        &mut self,
+        days: f64,
+        lanes: &EntityArray<Lane>,
+        systems: &EntityArray<System>,
+        refuels: F,
+    ) -> Vec<SystemIndex>
+    where
+        F: Fn(SystemIndex) -> bool,
+    {
+        let mut arrivals = vec![];
+        if self.progress == 0. && refuels(self.system) {
+            self.fuel = self.fuel.max(self.fuel_capacity);
+        }
+        let mut distance = self.speed * days;
+        while distance > 0. && !self.route.is_empty() {
+            let leg = self.route[0];
+            let length = leg.length(self.system, lanes, systems);
+            if self.progress == 0. && self.fuel < length * leg.fuel_per_distance() {
+                warn!("{} is out of fuel, stopping", self.name);
+                self.route.clear();
+                break;
+            }
+
+            let travelled = distance.min(length - self.progress);
+            self.progress += travelled;
+            self.fuel -= travelled * leg.fuel_per_distance();
+            distance -= travelled;
+            if self.progress >= length {
+                self.system = leg.end(self.system, lanes);
+                self.progress = 0.;
+                self.route.remove(0);
+                arrivals.push(self.system);
+                if refuels(self.system) {
+                    self.fuel = self.fuel.max(self.fuel_capacity);
+                }
+            }
+        }
+        if self.route.is_empty() {
+            self.destination = None;
+        }
+
+        // Interpolate the location along the current leg.
+        self.location = systems[self.system].location;
+        if let Some(&leg) = self.route.first() {
+            let next = systems[leg.end(self.system, lanes)].location;
+            let fraction = self.progress / leg.length(self.system, lanes, systems);
+            self.location = Point::new(
+                self.location.x + (next.x - self.location.x) * fraction,
+                self.location.y + (next.y - self.location.y) * fraction,
+            );
+        }
+        arrivals
+    }
+}
+
+/// Hop of a route from one system to the next.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Leg {
+    /// Along the given lane.
+    Lane(LaneIndex),
+    /// Straight to the given system, without following a lane.
+    Jump(SystemIndex),
+}
+
+impl Leg {
+    /// System reached when taking the leg from the given one.
+    pub fn end(self, from: SystemIndex, lanes: &EntityArray<Lane>) -> SystemIndex {
+        match self {
+            Leg::Lane(lane) => lanes[lane].other(from).unwrap_or(from),
+            Leg::Jump(to) => to,
+        }
+    }
+
+    /// Distance covered when taking the leg from the given system.
+    pub fn length(
+        self,
+        from: SystemIndex,
+        lanes: &EntityArray<Lane>,
+        systems: &EntityArray<System>,
+    ) -> f64 {
+        match self {
+            Leg::Lane(lane) => lanes[lane].length,
+            Leg::Jump(to) => systems[from].location.distance(&systems[to].location),
+        }
+    }
+
+    /// Fuel used per unit of distance along the leg.
+    pub fn fuel_per_distance(self) -> f64 {
+        match self {
+            Leg::Lane(_) => Ship::FUEL_PER_DISTANCE,
+            Leg::Jump(_) => Ship::JUMP_FUEL_PER_DISTANCE,
+        }
+    }
+}
+
+impl Entity for Ship {
+    type Index = ShipIndex;
+}
+
+/// Index type for Ship
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShipIndex(usize);
+
+impl Into<usize> for ShipIndex {
+    fn into(self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for ShipIndex {
+    fn from(index: usize) -> ShipIndex {
+        ShipIndex(index)
+    }
+}
+
+impl EntityIndex for ShipIndex {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_travel() {
+        let mut systems = EntityArray::new();
+        let mut lanes = EntityArray::new();
+        for x in &[0., 10., 30.] {
+            systems.insert(
+                System::builder()
+                    .location(Point::new(*x, 0.))
+                    .name("")
+                    .build(),
+            );
+        }
+        let first = lanes.insert(Lane::new(SystemIndex::from(0), SystemIndex::from(1), 10.));
+        let second = lanes.insert(Lane::new(SystemIndex::from(1), SystemIndex::from(2), 20.));
+
+        let mut ship = Ship::builder()
+            .name("Test")
+            .location(Point::origin())
+            .system(SystemIndex::from(0))
+            .speed(5.)
+            .fuel(100.)
+            .build();
+        ship.order(
+            vec![Leg::Lane(first), Leg::Lane(second)],
+            SystemIndex::from(2),
+        );
+
+        let never = |_| false;
+        ship.advance(3., &lanes, &systems, never);
+        assert_eq!(ship.system, SystemIndex::from(1));
+        assert_eq!(ship.location, Point::new(15., 0.));
+        assert!(ship.travelling());
+
+        ship.advance(10., &lanes, &systems, never);
+        assert_eq!(ship.system, SystemIndex::from(2));
+        assert_eq!(ship.location, Point::new(30., 0.));
+        assert_eq!(ship.destination, None);
+        assert!((ship.fuel - (100. - 30. * Ship::FUEL_PER_DISTANCE)).abs() < 1e-9);
+
+        // Out of fuel ships stay put.
+        ship.fuel = 0.;
+        ship.order(vec![Leg::Lane(second)], SystemIndex::from(1));
+        ship.advance(1., &lanes, &systems, never);
+        assert_eq!(ship.location, Point::new(30., 0.));
+        assert!(!ship.travelling());
+
+        // Jumps use more fuel, and arriving at a refuelling system fills up.
+        ship.fuel = 5.;
+        ship.fuel_capacity = 10.;
+        ship.order(vec![Leg::Jump(SystemIndex::from(0))], SystemIndex::from(0));
+        ship.advance(3., &lanes, &systems, |_| false);
+        assert!((ship.fuel - (5. - 15. * Ship::JUMP_FUEL_PER_DISTANCE)).abs() < 1e-9);
+        ship.advance(3., &lanes, &systems, |system| {
+            system == SystemIndex::from(0)
+        });
+        assert_eq!(ship.location, Point::origin());
+        assert_eq!(ship.fuel, 10.);
+
+        // Docked ships fill up before setting out, as routes are planned.
+        ship.fuel = 0.;
+        ship.order(vec![Leg::Lane(first)], SystemIndex::from(1));
+        ship.advance(1., &lanes, &systems, |system| {
+            system == SystemIndex::from(0)
+        });
+        assert_eq!(ship.location, Point::new(5., 0.));
+        assert!((ship.fuel - (10. - 5. * Ship::FUEL_PER_DISTANCE)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reorder() {
+        let mut systems = EntityArray::new();
+        let mut lanes = EntityArray::new();
+        for x in &[0., 10., 30.] {
+            systems.insert(
+                System::builder()
+                    .location(Point::new(*x, 0.))
+                    .name("")
+                    .build(),
+            );
+        }
+        let first = lanes.insert(Lane::new(SystemIndex::from(0), SystemIndex::from(1), 10.));
+        let second = lanes.insert(Lane::new(SystemIndex::from(1), SystemIndex::from(2), 20.));
+
+        let mut ship = Ship::builder()
+            .name("Test")
+            .location(Point::origin())
+            .system(SystemIndex::from(0))
+            .speed(5.)
+            .fuel(100.)
+            .build();
+        assert_eq!(ship.departure(&lanes), SystemIndex::from(0));
+        ship.order(vec![Leg::Lane(first)], SystemIndex::from(1));
+        ship.advance(1., &lanes, &systems, |_| false);
+        assert_eq!(ship.location, Point::new(5., 0.));
+
+        // Turning back mid-lane plans from the end of the lane, which is reached first.
+        assert_eq!(ship.departure(&lanes), SystemIndex::from(1));
+        ship.order(vec![Leg::Lane(first)], SystemIndex::from(0));
+        assert_eq!(ship.route, vec![Leg::Lane(first), Leg::Lane(first)]);
+        ship.advance(1., &lanes, &systems, |_| false);
+        assert_eq!(ship.system, SystemIndex::from(1));
+        ship.advance(2., &lanes, &systems, |_| false);
+        assert_eq!(ship.location, Point::origin());
+        assert!(!ship.travelling());
+
+        // Re-ordering while stopped starts from where the ship is.
+        ship.order(
+            vec![Leg::Lane(first), Leg::Lane(second)],
+            SystemIndex::from(2),
+        );
+        assert_eq!(ship.route.len(), 2);
+    }
+}
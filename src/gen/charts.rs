@@ -0,0 +1,51 @@
+use super::*;
+
+use crate::knowledge::{Chart, Knowledge, Observer};
+
+/// Charts what the player and each faction know about the galaxy at the start.
+pub struct Charts;
+
+impl Stage for Charts {
+    fn name(&self) -> &'static str {
+        "charts"
+    }
+
+    fn run(&self, _: &Config, world: &mut World, _: Seed) {
+        world.charts.clear();
+
+        // Factions know their own systems, and those connected to them.
+        for (id, faction) in world.factions.iter().enumerate() {
+            let mut chart = Chart::new(
+                Observer::Faction(FactionIndex::from(id)),
+                world.systems.len(),
+            );
+            for &system in &faction.systems {
+                chart.learn(system, Knowledge::Visited);
+            }
+            for lane in world.lanes.iter() {
+                if chart.get(lane.from) == Knowledge::Visited {
+                    chart.learn(lane.to, Knowledge::Surveyed);
+                }
+                if chart.get(lane.to) == Knowledge::Visited {
+                    chart.learn(lane.from, Knowledge::Surveyed);
+                }
+            }
+            world.charts.push(chart);
+        }
+
+        // The player only knows what their ship can see.
+        if world.player.is_some() {
+            world
+                .charts
+                .push(Chart::new(Observer::Player, world.systems.len()));
+            world.explore();
+            info!(
+                "Player knows of {} systems",
+                world
+                    .charts
+                    .last()
+                    .map_or(0, |chart| chart.count(Knowledge::Detected))
+            );
+        }
+    }
+}
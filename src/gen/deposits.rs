@@ -0,0 +1,48 @@
+use super::*;
+
+use crate::{economy::Commodity, prospecting::Deposit};
+use rand::Rng;
+
+/// Hides resource deposits within planets and belts.
+pub struct Deposits;
+
+impl Deposits {
+    /// Chance of each kind of body holding a deposit of each commodity,
+    /// along with a multiplier of the deposit size.
+    fn odds(kind: BodyKind) -> &'static [(Commodity, f64, f64)] {
+        match kind {
+            BodyKind::Rocky => &[(Commodity::Ore, 0.6, 1.), (Commodity::Tech, 0.05, 0.05)],
+            BodyKind::Ocean => &[(Commodity::Food, 0.4, 1.), (Commodity::Ore, 0.2, 0.5)],
+            BodyKind::GasGiant => &[(Commodity::Fuel, 0.9, 3.)],
+            BodyKind::Ice => &[(Commodity::Fuel, 0.5, 1.), (Commodity::Ore, 0.3, 0.5)],
+            BodyKind::Belt => &[(Commodity::Ore, 0.9, 2.), (Commodity::Tech, 0.1, 0.1)],
+        }
+    }
+}
+
+impl Stage for Deposits {
+    fn name(&self) -> &'static str {
+        "deposits"
+    }
+
+    fn run(&self, _: &Config, world: &mut World, seed: Seed) {
+        let mut count = 0;
+        for (id, body) in world.bodies.iter_mut().enumerate() {
+            let mut rng = seed.index(id).rng();
+            body.deposits.clear();
+            for &(commodity, chance, size) in Deposits::odds(body.kind) {
+                if rng.gen::<f64>() < chance {
+                    let amount = (rng.gen_range(500., 5_000.) * size).round();
+                    body.deposits.push(Deposit {
+                        commodity,
+                        amount,
+                        initial: amount,
+                        depth: rng.gen_range(0.05, 1.),
+                    });
+                }
+            }
+            count += body.deposits.len();
+        }
+        info!("Hid {} deposits", count);
+    }
+}
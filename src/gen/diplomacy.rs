@@ -0,0 +1,444 @@
+use super::*;
+
+use crate::entity::faction::{Relations, Treaty, War};
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Evolving relations between factions: opinions shift with shared borders,
+/// governments and enemies, leading to alliances against strong neighbours,
+/// and wars which transfer systems until peace is signed.
+#[derive(Default)]
+pub struct Diplomacy {
+    graph: Option<LaneGraph>,
+}
+
+impl Diplomacy {
+    /// Yearly opinion change per shared border lane, capped by `MAX_FRICTION`.
+    const FRICTION: f64 = -1.;
+    const MAX_FRICTION: f64 = -20.;
+    /// Yearly opinion change between factions with the same government.
+    const KINSHIP: f64 = 5.;
+    /// Yearly opinion change between factions at war with the same enemy.
+    const COMMON_ENEMY: f64 = 15.;
+    /// Yearly random opinion change.
+    const NOISE: f64 = 10.;
+    /// Fraction of opinion lost each year, moving it towards neutral.
+    const DRIFT: f64 = 0.1;
+
+    /// Opinion below which wars may be declared.
+    const WAR_OPINION: f64 = -50.;
+    /// Yearly chance of declaring war when relations are bad enough.
+    const WAR_CHANCE: f64 = 0.5;
+    /// Opinion above which threatened factions ally.
+    const ALLIANCE_OPINION: f64 = 20.;
+    /// Strength ratio at which a neighbour is seen as a threat.
+    const THREAT: f64 = 1.5;
+
+    /// Battles per year along a shared front.
+    const BATTLES: f64 = 3.;
+    /// Fraction of the population lost when a system is conquered.
+    const CASUALTIES: f64 = 0.2;
+    /// Base yearly chance of peace, growing with each year of war.
+    const PEACE_CHANCE: f64 = 0.1;
+    /// Years of non-aggression following a peace treaty.
+    const TRUCE_YEARS: u64 = 20;
+    /// Chance of a conquered system being renamed after the conqueror's home.
+    const RENAME_CHANCE: f64 = 0.2;
+
+    /// Create a new diplomacy process.
+    pub fn new() -> Self {
+        Diplomacy::default()
+    }
+
+    /// Systems along each border between two factions, smallest faction index first.
+    fn borders(
+        world: &World,
+        graph: &LaneGraph,
+    ) -> HashMap<(FactionIndex, FactionIndex), Vec<(SystemIndex, SystemIndex)>> {
+        let mut borders: HashMap<_, Vec<_>> = HashMap::new();
+        for id in (0..world.systems.len()).map(SystemIndex::from) {
+            for &(other, _) in graph.neighbours(id) {
+                let a = world.systems[id].faction;
+                let b = world.systems[other].faction;
+                if let (Some(a), Some(b)) = (a, b) {
+                    if Into::<usize>::into(a) < b.into() {
+                        borders.entry((a, b)).or_default().push((id, other));
+                    }
+                }
+            }
+        }
+        borders
+    }
+
+    /// Update opinions between all pairs of factions.
+    fn opinions(
+        world: &mut World,
+        rng: &mut ChaChaRng,
+        years: f64,
+        borders: &HashMap<(FactionIndex, FactionIndex), Vec<(SystemIndex, SystemIndex)>>,
+    ) {
+        let count = world.factions.len();
+        for a in (0..count).map(FactionIndex::from) {
+            for b in (Into::<usize>::into(a) + 1..count).map(FactionIndex::from) {
+                let border = borders.get(&(a, b)).map_or(0, |border| border.len());
+                let mut change = (Self::FRICTION * border as f64).max(Self::MAX_FRICTION);
+                if world.factions[a].government == world.factions[b].government {
+                    change += Self::KINSHIP;
+                }
+                let common_enemy = (0..count).map(FactionIndex::from).any(|enemy| {
+                    world.relations.at_war(a, enemy) && world.relations.at_war(b, enemy)
+                });
+                if common_enemy {
+                    change += Self::COMMON_ENEMY;
+                }
+                change += rng.gen_range(-Self::NOISE, Self::NOISE);
+
+                let relation = world.relations.get_mut(a, b);
+                relation.opinion = ((relation.opinion + change * years)
+                    * (1. - Self::DRIFT * years))
+                    .clamp(-100., 100.);
+            }
+        }
+    }
+
+    /// Declare war on hated neighbours, with allies joining the defender.
+    fn declare_wars(
+        world: &mut World,
+        rng: &mut ChaChaRng,
+        years: f64,
+        borders: &HashMap<(FactionIndex, FactionIndex), Vec<(SystemIndex, SystemIndex)>>,
+    ) {
+        let strength = strengths(world);
+        let mut pairs: Vec<_> = borders.keys().cloned().collect();
+        pairs.sort_by_key(|&(a, b)| (Into::<usize>::into(a), Into::<usize>::into(b)));
+        for (a, b) in pairs {
+            let relation = world.relations.get(a, b);
+            let truce = match relation.treaty {
+                Treaty::NonAggression { until } => until > world.day,
+                Treaty::Alliance => true,
+                Treaty::None => false,
+            };
+            if relation.war.is_some() || truce || relation.opinion > Self::WAR_OPINION {
+                continue;
+            }
+
+            // The stronger side is the aggressor, if eager enough.
+            let (aggressor, defender) = if strength(a) >= strength(b) {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            let chance = Self::WAR_CHANCE * world.factions[aggressor].government.aggression();
+            if rng.gen::<f64>() >= chance * years {
+                continue;
+            }
+            Self::start_war(world, aggressor, defender);
+
+            // Allies of the defender join in.
+            for ally in (0..world.factions.len()).map(FactionIndex::from) {
+                if ally != aggressor
+                    && world.relations.allied(ally, defender)
+                    && !world.relations.allied(ally, aggressor)
+                    && !world.relations.at_war(ally, aggressor)
+                {
+                    Self::start_war(world, ally, aggressor);
+                }
+            }
+        }
+    }
+
+    fn start_war(world: &mut World, aggressor: FactionIndex, defender: FactionIndex) {
+        let day = world.day;
+        let relation = world.relations.get_mut(aggressor, defender);
+        relation.treaty = Treaty::None;
+        relation.war = Some(War {
+            aggressor,
+            since: day,
+        });
+        world.record(EventKind::WarDeclared {
+            aggressor,
+            defender,
+        });
+    }
+
+    /// Form alliances between friendly factions where one is threatened by a
+    /// neighbour the other can reach, and break up alliances which soured.
+    fn alliances(
+        world: &mut World,
+        borders: &HashMap<(FactionIndex, FactionIndex), Vec<(SystemIndex, SystemIndex)>>,
+    ) {
+        let strength = strengths(world);
+        let count = world.factions.len();
+        let neighbours = |a: FactionIndex, b: FactionIndex| {
+            let (a, b) = if Into::<usize>::into(a) < b.into() {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            borders.contains_key(&(a, b))
+        };
+        let threatened = |a: FactionIndex, by: FactionIndex| {
+            a != by && neighbours(a, by) && strength(by) > strength(a) * Self::THREAT
+        };
+
+        for a in (0..count).map(FactionIndex::from) {
+            for b in (Into::<usize>::into(a) + 1..count).map(FactionIndex::from) {
+                let relation = world.relations.get(a, b);
+                if relation.treaty == Treaty::Alliance {
+                    if relation.opinion < 0. {
+                        world.relations.get_mut(a, b).treaty = Treaty::None;
+                        world.record(EventKind::AllianceBroken { factions: (a, b) });
+                    }
+                    continue;
+                }
+                if relation.war.is_some() || relation.opinion < Self::ALLIANCE_OPINION {
+                    continue;
+                }
+                let reachable = |from: FactionIndex, threat: FactionIndex| {
+                    neighbours(from, threat) || neighbours(a, b)
+                };
+                let common_threat = (0..count).map(FactionIndex::from).any(|threat| {
+                    threat != a
+                        && threat != b
+                        && ((threatened(a, threat) && reachable(b, threat))
+                            || (threatened(b, threat) && reachable(a, threat)))
+                });
+                if common_threat {
+                    world.relations.get_mut(a, b).treaty = Treaty::Alliance;
+                    world.record(EventKind::AllianceFormed { factions: (a, b) });
+                }
+            }
+        }
+    }
+
+    /// Fight battles along the fronts of each war, transferring systems,
+    /// and sign peace as wars drag on.
+    fn wars(
+        world: &mut World,
+        rng: &mut ChaChaRng,
+        years: f64,
+        borders: &HashMap<(FactionIndex, FactionIndex), Vec<(SystemIndex, SystemIndex)>>,
+    ) {
+        let strength = strengths(world);
+        let mut fronts: Vec<_> = borders
+            .iter()
+            .filter(|(&(a, b), _)| world.relations.at_war(a, b))
+            .collect();
+        fronts.sort_by_key(|(&(a, b), _)| (Into::<usize>::into(a), Into::<usize>::into(b)));
+
+        for (&(a, b), front) in fronts {
+            if rng.gen::<f64>() >= Self::BATTLES * years {
+                continue;
+            }
+            let (winner, loser, side) =
+                if rng.gen::<f64>() * (strength(a) + strength(b)) < strength(a) {
+                    (a, b, 1)
+                } else {
+                    (b, a, 0)
+                };
+            // Take one of the loser's systems along the front.
+            let (from, to) = front[rng.gen_range(0, front.len())];
+            let system = if side == 1 { to } else { from };
+            if world.systems[system].faction != Some(loser) {
+                continue;
+            }
+            let population = &mut world.systems[system].population;
+            *population = (*population as f64 * (1. - Self::CASUALTIES)) as u64;
+            world.claim(system, winner);
+            world.record(EventKind::SystemConquered {
+                system,
+                from: loser,
+                to: winner,
+            });
+            if rng.gen::<f64>() < Self::RENAME_CHANCE {
+                Self::rename(world, system, winner);
+            }
+
+            if world.factions[loser].systems.is_empty() {
+                world.record(EventKind::FactionDefeated { faction: loser });
+                for other in (0..world.factions.len()).map(FactionIndex::from) {
+                    let relation = world.relations.get_mut(loser, other);
+                    relation.war = None;
+                    relation.treaty = Treaty::None;
+                }
+            }
+        }
+
+        // Wars grow more likely to end the longer they last.
+        for a in (0..world.factions.len()).map(FactionIndex::from) {
+            for b in (Into::<usize>::into(a) + 1..world.factions.len()).map(FactionIndex::from) {
+                let since = match &world.relations.get(a, b).war {
+                    Some(war) => war.since,
+                    None => continue,
+                };
+                let duration = (world.day - since).days() as f64 / DAYS_PER_YEAR as f64;
+                if rng.gen::<f64>() < Self::PEACE_CHANCE * (1. + duration) * years {
+                    let until = world.day + Duration::years(Self::TRUCE_YEARS);
+                    let relation = world.relations.get_mut(a, b);
+                    relation.war = None;
+                    relation.treaty = Treaty::NonAggression { until };
+                    relation.opinion = relation.opinion.max(-20.);
+                    world.record(EventKind::PeaceSigned { factions: (a, b) });
+                }
+            }
+        }
+    }
+
+    /// Rename the system after the home of the faction, numbered after any
+    /// previously renamed systems.
+    fn rename(world: &mut World, system: SystemIndex, faction: FactionIndex) {
+        let home = world.systems[world.factions[faction].home].name.clone();
+        if home.is_empty() {
+            return;
+        }
+        let prefix = format!("{} ", home);
+        let count = world
+            .systems
+            .iter()
+            .filter(|other| other.name.starts_with(&prefix))
+            .count();
+        let name = format!("{}{}", prefix, roman(count + 2));
+        let from = ::std::mem::replace(&mut world.systems[system].name, name.clone());
+        world.record(EventKind::Renamed {
+            system,
+            from,
+            to: name,
+        });
+    }
+
+    /// Mark systems along the fronts of ongoing wars as contested.
+    fn fronts(
+        world: &mut World,
+        borders: &HashMap<(FactionIndex, FactionIndex), Vec<(SystemIndex, SystemIndex)>>,
+    ) {
+        for system in world.systems.iter_mut() {
+            system.contested = false;
+        }
+        for (&(a, b), front) in borders {
+            if world.relations.at_war(a, b) {
+                for &(from, to) in front {
+                    world.systems[from].contested = true;
+                    world.systems[to].contested = true;
+                }
+            }
+        }
+    }
+}
+
+impl Process for Diplomacy {
+    fn name(&self) -> &'static str {
+        "diplomacy"
+    }
+
+    fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64) {
+        if world.relations.len() != world.factions.len() {
+            world.relations = Relations::new(world.factions.len());
+        }
+        let graph = self.graph.get_or_insert_with(|| LaneGraph::new(world));
+        let years = days as f64 / DAYS_PER_YEAR as f64;
+
+        let borders = Diplomacy::borders(world, graph);
+        Diplomacy::opinions(world, rng, years, &borders);
+        Diplomacy::alliances(world, &borders);
+        Diplomacy::declare_wars(world, rng, years, &borders);
+        Diplomacy::wars(world, rng, years, &borders);
+
+        let borders = Diplomacy::borders(world, graph);
+        Diplomacy::fronts(world, &borders);
+    }
+}
+
+/// Format the number using roman numerals.
+fn roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut text = String::new();
+    for &(value, numeral) in NUMERALS.iter() {
+        while number >= value {
+            text.push_str(numeral);
+            number -= value;
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_war() {
+        // A strong and a weak faction along a chain of systems.
+        let mut world = World::new();
+        for x in 0..4 {
+            let mut system = System::builder()
+                .location(Point::new(x as f64, 0.))
+                .name("")
+                .build();
+            system.population = if x < 2 { 1_000_000 } else { 1_000 };
+            let id = world.systems.insert(system);
+            if x > 0 {
+                world
+                    .lanes
+                    .insert(Lane::new(SystemIndex::from(x - 1), id, 1.));
+            }
+        }
+        for home in &[0, 3] {
+            let faction = Faction::builder()
+                .name("")
+                .color((0, 0, 0))
+                .government(Government::Military)
+                .home(SystemIndex::from(*home))
+                .build();
+            world.factions.insert(faction);
+        }
+        let (strong, weak) = (FactionIndex::from(0), FactionIndex::from(1));
+        for (system, faction) in &[(0, strong), (1, strong), (2, weak), (3, weak)] {
+            world.claim(SystemIndex::from(*system), *faction);
+        }
+
+        let mut rng = Seed::new(0).rng();
+        let mut diplomacy = Diplomacy::new();
+        for _ in 0..100 {
+            if world.relations.len() == 2 {
+                world.relations.get_mut(strong, weak).opinion = -100.;
+            }
+            diplomacy.step(&mut world, &mut rng, 30);
+            world.day += Duration(30);
+        }
+
+        assert!(world.history.iter().any(|event| event.kind
+            == EventKind::WarDeclared {
+                aggressor: strong,
+                defender: weak,
+            }));
+        assert!(world
+            .history
+            .iter()
+            .any(|event| matches!(event.kind, EventKind::SystemConquered { .. })));
+        for id in (0..world.systems.len()).map(SystemIndex::from) {
+            let owner = world.systems[id].faction.unwrap();
+            assert!(world.factions[owner].systems.contains(&id));
+        }
+    }
+
+    #[test]
+    fn test_roman() {
+        assert_eq!(roman(2), "II");
+        assert_eq!(roman(14), "XIV");
+        assert_eq!(roman(1994), "MCMXCIV");
+    }
+}
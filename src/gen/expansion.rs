@@ -0,0 +1,150 @@
+use super::*;
+
+use rand::Rng;
+
+/// Factions claiming unowned neighbouring systems, preferring populous ones
+/// close to home. Systems of rivals can only be taken by war, see `Diplomacy`.
+pub struct Expansion {
+    rate: f64,
+    graph: Option<LaneGraph>,
+}
+
+impl Expansion {
+    /// Population of newly founded colonies.
+    const COLONISTS: u64 = 1_000;
+
+    /// Create a new expansion process using the configured rate.
+    pub fn new(config: &Config) -> Self {
+        Expansion {
+            rate: config.simulation.expansion_rate,
+            graph: None,
+        }
+    }
+}
+
+/// Total population of the systems owned by each faction, indexable by faction.
+pub fn strengths(world: &World) -> impl Fn(FactionIndex) -> f64 {
+    let strengths: Vec<f64> = world
+        .factions
+        .iter()
+        .map(|faction| {
+            faction
+                .systems
+                .iter()
+                .map(|&system| world.systems[system].population as f64)
+                .sum()
+        })
+        .collect();
+    move |faction| strengths[Into::<usize>::into(faction)]
+}
+
+impl Process for Expansion {
+    fn name(&self) -> &'static str {
+        "expansion"
+    }
+
+    fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64) {
+        let graph = self.graph.get_or_insert_with(|| LaneGraph::new(world));
+        let years = days as f64 / DAYS_PER_YEAR as f64;
+
+        for id in (0..world.factions.len()).map(FactionIndex::from) {
+            let faction = &world.factions[id];
+            if rng.gen::<f64>() >= self.rate * years * faction.government.aggression() {
+                continue;
+            }
+
+            let home = &world.systems[faction.home].location;
+            let mut best = None;
+            let mut best_score = 0.;
+            for &system in &faction.systems {
+                for &(other, _) in graph.neighbours(system) {
+                    let target = &world.systems[other];
+                    if target.faction.is_some() {
+                        continue;
+                    }
+                    let score = (1. + (1. + target.population as f64 / 1_000.).ln())
+                        / (1. + target.location.distance(home) / 10.);
+                    if score > best_score {
+                        best_score = score;
+                        best = Some(other);
+                    }
+                }
+            }
+
+            if let Some(target) = best {
+                if world.systems[target].population == 0 {
+                    world.systems[target].population = Self::COLONISTS;
+                    world.record(EventKind::Colonised {
+                        system: target,
+                        faction: id,
+                    });
+                }
+                world.claim(target, id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansion() {
+        let mut config: Config = toml::from_str(include_str!("../../Config.toml")).unwrap();
+        config.simulation.expansion_rate = 1_000.;
+
+        // A home system with an empty neighbour and a populous one further away.
+        let mut world = World::new();
+        for &(x, population) in &[(0., 10_000), (1., 0), (2., 100_000)] {
+            let mut system = System::builder()
+                .location(Point::new(x, 0.))
+                .name("")
+                .build();
+            system.population = population;
+            world.systems.insert(system);
+        }
+        let home = SystemIndex::from(0);
+        for other in 1..3 {
+            world
+                .lanes
+                .insert(Lane::new(home, SystemIndex::from(other), 1.));
+        }
+        let faction = Faction::builder()
+            .name("")
+            .color((0, 0, 0))
+            .government(Government::Military)
+            .home(home)
+            .build();
+        let id = world.factions.insert(faction);
+        world.claim(home, id);
+
+        let mut rng = Seed::new(0).rng();
+        let mut expansion = Expansion::new(&config);
+        let mut claims = vec![];
+        for _ in 0..3 {
+            expansion.step(&mut world, &mut rng, 365);
+            claims.push(world.factions[id].systems.len());
+        }
+
+        // The populous system is claimed first, then the empty one is colonised.
+        assert_eq!(claims, vec![2, 3, 3]);
+        assert_eq!(world.factions[id].systems[1], SystemIndex::from(2));
+        assert_eq!(world.systems[SystemIndex::from(2)].population, 100_000);
+        assert_eq!(
+            world.systems[SystemIndex::from(1)].population,
+            Expansion::COLONISTS
+        );
+        assert_eq!(world.history.len(), 1);
+        assert!(
+            world.history[0].kind
+                == EventKind::Colonised {
+                    system: SystemIndex::from(1),
+                    faction: id,
+                }
+        );
+        for &system in &world.factions[id].systems {
+            assert_eq!(world.systems[system].faction, Some(id));
+        }
+    }
+}
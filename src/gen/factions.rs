@@ -0,0 +1,115 @@
+use super::*;
+
+use crate::entity::faction::Relations;
+use rand::{seq::SliceRandom, Rng};
+
+/// Founds factions in the most populous settled systems, spaced apart.
+pub struct Factions;
+
+impl Stage for Factions {
+    fn name(&self) -> &'static str {
+        "factions"
+    }
+
+    fn run(&self, config: &Config, world: &mut World, seed: Seed) {
+        let mut rng = seed.rng();
+
+        world.factions = EntityArray::new();
+        for system in world.systems.iter_mut() {
+            system.faction = None;
+            system.contested = false;
+        }
+
+        let mut settled: Vec<SystemIndex> = (0..world.systems.len())
+            .map(SystemIndex::from)
+            .filter(|&id| world.systems[id].population > 0)
+            .collect();
+        settled.sort_by_key(|&id| ::std::cmp::Reverse(world.systems[id].population));
+
+        let mut homes: Vec<SystemIndex> = vec![];
+        for id in settled {
+            if homes.len() == config.simulation.number_of_factions {
+                break;
+            }
+            let location = &world.systems[id].location;
+            if homes.iter().all(|&home| {
+                world.systems[home].location.distance(location) >= config.simulation.faction_spacing
+            }) {
+                homes.push(id);
+            }
+        }
+
+        // Spread colors evenly around the hue circle.
+        let offset = rng.gen::<f64>();
+        for (i, &home) in homes.iter().enumerate() {
+            let hue = (offset + i as f64 / homes.len() as f64) % 1.;
+            let faction = Faction::builder()
+                .name("")
+                .color(hsv_to_rgb(hue, 0.7, 0.9))
+                .government(*Government::ALL.choose(&mut rng).unwrap())
+                .home(home)
+                .build();
+            let id = world.factions.insert(faction);
+            world.claim(home, id);
+        }
+        world.relations = Relations::new(world.factions.len());
+        info!("Founded {} factions", world.factions.len());
+    }
+}
+
+/// Convert a color given as hue, saturation and value in [0, 1] to RGB.
+fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> (u8, u8, u8) {
+    let sector = (hue * 6.).floor();
+    let fraction = hue * 6. - sector;
+    let p = value * (1. - saturation);
+    let q = value * (1. - fraction * saturation);
+    let t = value * (1. - (1. - fraction) * saturation);
+    let (r, g, b) = match sector as u8 % 6 {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    };
+    ((r * 255.) as u8, (g * 255.) as u8, (b * 255.) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factions() {
+        let mut config: Config = toml::from_str(include_str!("../../Config.toml")).unwrap();
+        config.simulation.number_of_factions = 3;
+        config.simulation.faction_spacing = 10.;
+
+        // The second system is too close to the first, the last is unsettled.
+        let mut world = World::new();
+        for &(x, population) in &[(0., 500), (1., 400), (20., 300), (40., 200), (60., 0)] {
+            let mut system = System::builder()
+                .location(Point::new(x, 0.))
+                .name("")
+                .build();
+            system.population = population;
+            world.systems.insert(system);
+        }
+
+        // Founding again replaces the previous factions.
+        for _ in 0..2 {
+            Factions.run(&config, &mut world, Seed::new(42));
+            let homes: Vec<usize> = world.factions.iter().map(|f| f.home.into()).collect();
+            assert_eq!(homes, vec![0, 2, 3]);
+            for (id, faction) in world.factions.iter().enumerate() {
+                assert_eq!(faction.systems, vec![faction.home]);
+                assert_eq!(
+                    world.systems[faction.home].faction,
+                    Some(FactionIndex::from(id))
+                );
+            }
+            assert_eq!(world.systems[SystemIndex::from(1)].faction, None);
+            assert_eq!(world.systems[SystemIndex::from(4)].faction, None);
+        }
+    }
+}
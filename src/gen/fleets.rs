@@ -0,0 +1,271 @@
+use super::*;
+
+use crate::{
+    entity::ship::{Role, Ship, ShipIndex},
+    outfitting::{Hull, Module, ModuleKind, Outfit},
+};
+use rand::{seq::SliceRandom, Rng};
+
+/// Ships of the factions going about their business: traders flying between
+/// inhabited systems, patrols guarding their territory and pirates raiding
+/// dangerous space. Orders are given once a day, while the ships move along
+/// their routes in game time like the player's.
+#[derive(Default)]
+pub struct Fleets {
+    graph: Option<LaneGraph>,
+}
+
+impl Fleets {
+    /// Systems owned per ship of a faction.
+    const SYSTEMS_PER_SHIP: usize = 10;
+    /// Most ships of a single faction.
+    const MAX_SHIPS: usize = 12;
+    /// Chance per day of a faction with room in its fleet launching a ship.
+    const LAUNCH_CHANCE: f64 = 0.2;
+    /// Chance per day of an idle ship setting out again.
+    const DEPART_CHANCE: f64 = 0.3;
+    /// Furthest travel distance of a single voyage.
+    const MAX_DISTANCE: f64 = 60.;
+    /// Danger of the systems pirates prey on, at least.
+    const PIRATE_DANGER: f64 = 0.4;
+
+    /// Create a new fleet process.
+    pub fn new() -> Self {
+        Fleets::default()
+    }
+
+    /// Hull shared by the ships of all fleets.
+    fn hull() -> Hull {
+        use self::ModuleKind::*;
+        Hull {
+            mass: 40.,
+            max_mass: 160.,
+            power: 25.,
+            slots: vec![
+                Drive, FuelTank, FuelTank, CargoHold, CargoHold, Weapon, Shield,
+            ],
+        }
+    }
+
+    /// Outfit of ships of the role: traders carry goods over long distances,
+    /// patrols are faster and armed, and pirates trade range for speed.
+    fn outfit(role: Role) -> Outfit {
+        use self::ModuleKind::*;
+        let module = |kind, grade| Some(Module::new(kind, grade));
+        let modules = match role {
+            Role::Trader => vec![
+                module(Drive, 1),
+                module(FuelTank, 2),
+                module(FuelTank, 2),
+                module(CargoHold, 1),
+                module(CargoHold, 1),
+                None,
+                None,
+            ],
+            Role::Patrol => vec![
+                module(Drive, 2),
+                module(FuelTank, 1),
+                module(FuelTank, 1),
+                None,
+                None,
+                module(Weapon, 1),
+                module(Shield, 1),
+            ],
+            Role::Pirate => vec![
+                module(Drive, 2),
+                module(FuelTank, 1),
+                None,
+                None,
+                None,
+                module(Weapon, 2),
+                None,
+            ],
+        };
+        Outfit::new(Fleets::hull(), modules)
+    }
+
+    /// Role of the next ship of a fleet, keeping roughly half of it traders
+    /// and the rest split between patrols and pirates.
+    fn next_role(fleet: &[Role]) -> Role {
+        let count = |role| fleet.iter().filter(|&&other| other == role).count();
+        if count(Role::Trader) * 2 <= fleet.len() {
+            Role::Trader
+        } else if count(Role::Patrol) * 2 <= count(Role::Pirate) * 3 {
+            Role::Patrol
+        } else {
+            Role::Pirate
+        }
+    }
+
+    /// Launch new ships from the home systems of factions with room in their fleet.
+    fn launch<R: Rng>(world: &mut World, rng: &mut R, days: u64) {
+        for id in 0..world.factions.len() {
+            let faction = FactionIndex::from(id);
+            let fleet: Vec<Role> = world
+                .ships
+                .iter()
+                .filter(|ship| ship.faction == Some(faction))
+                .filter_map(|ship| ship.role)
+                .collect();
+            let size = (world.factions[faction].systems.len() / Fleets::SYSTEMS_PER_SHIP)
+                .min(Fleets::MAX_SHIPS);
+            if fleet.len() >= size || rng.gen::<f64>() >= Fleets::LAUNCH_CHANCE * days as f64 {
+                continue;
+            }
+
+            let role = Fleets::next_role(&fleet);
+            let home = world.factions[faction].home;
+            let mut ship = Ship::builder()
+                .name(format!(
+                    "{} {} {}",
+                    world.factions[faction].name,
+                    role.name(),
+                    fleet.len() + 1
+                ))
+                .location(world.systems[home].location)
+                .system(home)
+                .speed(0.)
+                .fuel(f64::INFINITY)
+                .faction(Some(faction))
+                .role(Some(role))
+                .build();
+            // Stats derive from the outfit, starting with a full tank.
+            ship.refit(Fleets::outfit(role));
+            debug!("Launched {}", ship.name);
+            world.ships.insert(ship);
+        }
+    }
+
+    /// Whether a ship of the given faction and role would head for the system.
+    fn suits(
+        world: &World,
+        faction: Option<FactionIndex>,
+        role: Role,
+        system: SystemIndex,
+    ) -> bool {
+        match role {
+            Role::Trader => world.systems[system].population > 0,
+            Role::Patrol => world.systems[system].faction == faction,
+            Role::Pirate => world.danger(system) >= Fleets::PIRATE_DANGER,
+        }
+    }
+}
+
+impl Process for Fleets {
+    fn name(&self) -> &'static str {
+        "fleets"
+    }
+
+    fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64) {
+        Fleets::launch(world, rng, days);
+
+        let graph = self.graph.get_or_insert_with(|| LaneGraph::new(world));
+        for index in 0..world.ships.len() {
+            let ship = &world.ships[ShipIndex::from(index)];
+            let role = match ship.role {
+                Some(role) if !ship.travelling() => role,
+                _ => continue,
+            };
+            if rng.gen::<f64>() >= Fleets::DEPART_CHANCE * days as f64 {
+                continue;
+            }
+
+            // Sorted, so that choices only depend on the random stream.
+            let mut targets: Vec<SystemIndex> = graph
+                .paths(ship.system, Fleets::MAX_DISTANCE)
+                .systems()
+                .map(|(system, _)| system)
+                .filter(|&system| system != ship.system)
+                .filter(|&system| Fleets::suits(world, ship.faction, role, system))
+                .collect();
+            targets.sort_by_key(|&system| Into::<usize>::into(system));
+            targets.shuffle(rng);
+
+            // Head for the first target the ship has the range and fuel to reach.
+            let order = targets.into_iter().find_map(|target| {
+                graph
+                    .plan(world, ship, target, |_| true)
+                    .ok()
+                    .map(|route| (route, target))
+            });
+            if let Some((route, target)) = order {
+                world.ships[ShipIndex::from(index)].order(route, target);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::faction::Government;
+
+    #[test]
+    fn test_next_role() {
+        let mut fleet = vec![];
+        for _ in 0..10 {
+            let role = Fleets::next_role(&fleet);
+            fleet.push(role);
+        }
+        let count = |role| fleet.iter().filter(|&&other| other == role).count();
+        assert_eq!(count(Role::Trader), 5);
+        assert_eq!(count(Role::Patrol), 3);
+        assert_eq!(count(Role::Pirate), 2);
+    }
+
+    #[test]
+    fn test_outfit() {
+        for &role in &[Role::Trader, Role::Patrol, Role::Pirate] {
+            let outfit = Fleets::outfit(role);
+            assert!(outfit.validate().is_ok());
+            let stats = outfit.stats();
+            assert!(stats.fuel_capacity > 0. && stats.jump_range > 0.);
+        }
+    }
+
+    #[test]
+    fn test_launch() {
+        // A faction owning a line of inhabited systems, with room for one ship.
+        let mut world = World::new();
+        for x in 0..Fleets::SYSTEMS_PER_SHIP {
+            let mut system = System::builder()
+                .location(Point::new(x as f64 * 5., 0.))
+                .name("")
+                .build();
+            system.population = 1_000;
+            let id = world.systems.insert(system);
+            if x > 0 {
+                world
+                    .lanes
+                    .insert(Lane::new(SystemIndex::from(x - 1), id, 5.));
+            }
+        }
+        let home = SystemIndex::from(0);
+        let faction = world.factions.insert(
+            Faction::builder()
+                .name("Vega Republic")
+                .color((0, 0, 0))
+                .government(Government::Democracy)
+                .home(home)
+                .build(),
+        );
+        for id in 0..world.systems.len() {
+            world.claim(SystemIndex::from(id), faction);
+        }
+
+        // A month is long enough to be sure of launching and setting out.
+        let mut rng = Seed::new(0).rng();
+        let mut fleets = Fleets::new();
+        fleets.step(&mut world, &mut rng, 30);
+        fleets.step(&mut world, &mut rng, 30);
+        assert_eq!(world.ships.len(), 1);
+        let ship = ShipIndex::from(0);
+        assert_eq!(world.ships[ship].faction, Some(faction));
+        assert_eq!(world.ships[ship].role, Some(Role::Trader));
+        assert!(world.ships[ship].travelling());
+        assert!(world.ships[ship].fuel <= world.ships[ship].fuel_capacity);
+
+        world.travel(1.);
+        assert!(world.ships[ship].location != world.systems[home].location);
+    }
+}
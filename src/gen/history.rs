@@ -0,0 +1,25 @@
+use super::*;
+
+/// Simulates the world for the configured time.
+pub struct History;
+
+impl Stage for History {
+    fn name(&self) -> &'static str {
+        "history"
+    }
+
+    fn run(&self, config: &Config, world: &mut World, seed: Seed) {
+        info!("Simulating the world for {} years", config.simulation.years);
+        world.day = Date::default();
+        world.history.clear();
+        let mut simulation =
+            Simulation::new(seed.derive("simulation"), config.simulation.step_days.get());
+        simulation.register(Box::new(PopulationGrowth::new(config)));
+        simulation.register(Box::new(Markets));
+        simulation.register(Box::new(Trade::new()));
+        simulation.register(Box::new(Expansion::new(config)));
+        simulation.register(Box::new(Diplomacy::new()));
+        simulation.register(Box::new(Incidents));
+        simulation.run(world, config.simulation.years * DAYS_PER_YEAR);
+    }
+}
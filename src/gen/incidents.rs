@@ -0,0 +1,75 @@
+use super::*;
+
+use crate::{
+    economy::Commodity,
+    history::{Disaster, Discovery},
+};
+use rand::{seq::SliceRandom, Rng};
+
+/// Random discoveries and disasters in populous systems.
+pub struct Incidents;
+
+impl Incidents {
+    /// Smallest population for incidents to be noteworthy.
+    const MIN_POPULATION: u64 = 1_000_000;
+    /// Yearly chance of a discovery per system.
+    const DISCOVERY_CHANCE: f64 = 0.0005;
+    /// Yearly chance of each disaster per system, with the range of the
+    /// population killed.
+    const DISASTERS: [(Disaster, f64, (f64, f64)); 3] = [
+        (Disaster::Plague, 0.0005, (0.1, 0.3)),
+        (Disaster::Quake, 0.0005, (0.01, 0.05)),
+        (Disaster::Flare, 0.0001, (0.05, 0.2)),
+    ];
+    /// Yearly chance of famine in systems out of food.
+    const FAMINE_CHANCE: f64 = 0.01;
+    const FAMINE_DEATHS: (f64, f64) = (0.05, 0.15);
+}
+
+impl Process for Incidents {
+    fn name(&self) -> &'static str {
+        "incidents"
+    }
+
+    fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64) {
+        let years = days as f64 / DAYS_PER_YEAR as f64;
+        for id in (0..world.systems.len()).map(SystemIndex::from) {
+            let system = &world.systems[id];
+            if system.population < Self::MIN_POPULATION {
+                continue;
+            }
+
+            // Bright stars flare more often.
+            let mut disasters = Self::DISASTERS.to_vec();
+            disasters[2].1 *= system.star.luminosity().max(1.);
+            let food = system.market.good(Commodity::Food);
+            if food.stock <= 0. && food.consumption > food.production {
+                disasters.push((Disaster::Famine, Self::FAMINE_CHANCE, Self::FAMINE_DEATHS));
+            }
+
+            if rng.gen::<f64>() < Self::DISCOVERY_CHANCE * years {
+                let discovery = *[Discovery::Ruins, Discovery::Derelict, Discovery::Anomaly]
+                    .choose(rng)
+                    .unwrap();
+                world.record(EventKind::Discovered {
+                    system: id,
+                    discovery,
+                });
+            }
+
+            for (disaster, chance, (min, max)) in disasters {
+                if rng.gen::<f64>() < chance * years {
+                    let population = &mut world.systems[id].population;
+                    let deaths = (*population as f64 * rng.gen_range(min, max)) as u64;
+                    *population -= deaths;
+                    world.record(EventKind::Disaster {
+                        system: id,
+                        disaster,
+                        deaths,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+use std::collections::HashSet;
+
+/// Connects each system to its closest neighbours within the maximum lane length.
+pub struct Lanes;
+
+impl Stage for Lanes {
+    fn name(&self) -> &'static str {
+        "lanes"
+    }
+
+    fn run(&self, config: &Config, world: &mut World, _: Seed) {
+        let mut index = SpatialIndex::new(config.simulation.lane_length);
+        for (id, system) in world.systems.iter().enumerate() {
+            index.insert(system.location, id);
+        }
+
+        let mut lanes = EntityArray::new();
+        let mut connected = HashSet::new();
+        for (id, system) in world.systems.iter().enumerate() {
+            let neighbours = index
+                .within(&system.location, config.simulation.lane_length)
+                .into_iter()
+                .filter(|other| *other != id)
+                .take(config.simulation.lanes_per_system);
+            for other in neighbours {
+                if connected.insert((id.min(other), id.max(other))) {
+                    let from = SystemIndex::from(id);
+                    let to = SystemIndex::from(other);
+                    let length = system.location.distance(&world.systems[to].location);
+                    lanes.insert(Lane::new(from, to, length));
+                }
+            }
+        }
+        world.lanes = lanes;
+        info!("Generated {} lanes", world.lanes.len());
+    }
+}
@@ -0,0 +1,21 @@
+use super::*;
+
+/// Production, consumption and price updates of all system markets.
+pub struct Markets;
+
+impl Process for Markets {
+    fn name(&self) -> &'static str {
+        "markets"
+    }
+
+    fn step(&mut self, world: &mut World, _: &mut ChaChaRng, days: u64) {
+        let all_bodies = &world.bodies;
+        for system in world.systems.iter_mut() {
+            let bodies: Vec<&Body> = system.bodies.iter().map(|&id| &all_bodies[id]).collect();
+            system
+                .market
+                .set_rates(system.population, system.star, &bodies);
+            system.market.update(days);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+use crate::{config, knowledge::Observer};
+
+/// Extraction by all mining operations, paying their upkeep.
+pub struct Mining {
+    config: config::Mining,
+}
+
+impl Mining {
+    /// Create a new mining process using the configured yield curves.
+    pub fn new(config: &Config) -> Self {
+        Mining {
+            config: config.mining.clone(),
+        }
+    }
+}
+
+impl Process for Mining {
+    fn name(&self) -> &'static str {
+        "mining"
+    }
+
+    fn step(&mut self, world: &mut World, _: &mut ChaChaRng, days: u64) {
+        let days = days as f64;
+        for operation in world.operations.iter_mut() {
+            let deposits = &mut world.bodies[operation.body].deposits;
+            if operation.exhausted(deposits, &self.config) {
+                continue;
+            }
+
+            // The player's operations idle when upkeep can not be paid.
+            if operation.owner == Observer::Player {
+                let upkeep = operation.kind.upkeep() * days;
+                if world.credits < upkeep {
+                    continue;
+                }
+                world.credits -= upkeep;
+            }
+            operation.extract(deposits, &self.config, days);
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use super::*;
+
+use crate::{
+    economy::Commodity,
+    graph::Paths,
+    knowledge::Knowledge,
+    missions::{Mission, Objective, Status},
+};
+use rand::{seq::SliceRandom, Rng};
+
+/// Offers missions on the boards of systems known to the player, and resolves
+/// the missions the player has accepted.
+#[derive(Default)]
+pub struct Missions {
+    graph: Option<LaneGraph>,
+}
+
+impl Missions {
+    /// Most missions offered on a single board.
+    const BOARD_SIZE: usize = 4;
+    /// Chance per day of a board with room getting a new offer.
+    const OFFER_CHANCE: f64 = 0.1;
+    /// Furthest travel distance to the target of a mission.
+    const MAX_DISTANCE: f64 = 120.;
+    /// Radius of regions to survey or search.
+    const SURVEY_RADIUS: f64 = 10.;
+    const SEARCH_RADIUS: f64 = 20.;
+    /// Reward of every mission, and per unit of travel distance.
+    const BASE_REWARD: f64 = 100.;
+    const REWARD_PER_DISTANCE: f64 = 10.;
+    /// Travel distance per day assumed when setting deadlines.
+    const EXPECTED_SPEED: f64 = 2.5;
+    /// Days allowed on top of travelling.
+    const SLACK_DAYS: u64 = 20;
+    /// Chance per day and unit of danger of losing an unescorted convoy.
+    const LOSS_CHANCE: f64 = 0.2;
+
+    /// Create a new mission process.
+    pub fn new() -> Self {
+        Missions::default()
+    }
+
+    /// Generate a random mission offered at the origin, if a suitable target
+    /// is within reach.
+    fn generate<R: Rng>(
+        world: &World,
+        paths: &Paths,
+        origin: SystemIndex,
+        rng: &mut R,
+    ) -> Option<Mission> {
+        // Sorted, so that choices only depend on the random stream.
+        let mut reachable: Vec<SystemIndex> = paths
+            .systems()
+            .map(|(system, _)| system)
+            .filter(|&system| system != origin)
+            .collect();
+        reachable.sort_by_key(|&system| Into::<usize>::into(system));
+        let known = |knowledge: Knowledge| -> Vec<SystemIndex> {
+            reachable
+                .iter()
+                .cloned()
+                .filter(|&system| world.knowledge(system) >= knowledge)
+                .collect()
+        };
+        let detected = known(Knowledge::Detected);
+        let surveyed = known(Knowledge::Surveyed);
+        let populated: Vec<SystemIndex> = surveyed
+            .iter()
+            .cloned()
+            .filter(|&system| world.systems[system].population > 0)
+            .collect();
+
+        let (objective, target, difficulty) = match rng.gen_range(0, 4) {
+            0 => {
+                let destination = *populated.choose(rng)?;
+                // Carry what sells for the most at the destination, relative to here.
+                let ratio = |commodity: &Commodity| {
+                    world.market(destination).price(*commodity)
+                        / world.market(origin).price(*commodity)
+                };
+                let commodity = *Commodity::ALL
+                    .iter()
+                    .max_by(|a, b| ratio(a).partial_cmp(&ratio(b)).unwrap())?;
+                let units = (rng.gen_range(50., 200.) / 10_f64).round() * 10.;
+                let objective = Objective::Deliver {
+                    commodity,
+                    units,
+                    destination,
+                };
+                (objective, destination, 1.)
+            }
+            1 => {
+                let center = *detected.choose(rng)?;
+                let location = world.systems[center].location;
+                let unsurveyed = world.systems.iter().enumerate().any(|(id, system)| {
+                    system.location.distance(&location) <= Missions::SURVEY_RADIUS
+                        && world.knowledge(SystemIndex::from(id)) < Knowledge::Surveyed
+                });
+                if !unsurveyed {
+                    return None;
+                }
+                let objective = Objective::Survey {
+                    center,
+                    radius: Missions::SURVEY_RADIUS,
+                };
+                (objective, center, 1.2)
+            }
+            2 => {
+                let destination = *populated.choose(rng)?;
+                let objective = Objective::Escort {
+                    destination,
+                    convoy: None,
+                };
+                (objective, destination, 1.5)
+            }
+            _ => {
+                let lost = *reachable.choose(rng)?;
+                let location = world.systems[lost].location;
+                let nearby: Vec<SystemIndex> = surveyed
+                    .iter()
+                    .cloned()
+                    .filter(|&system| {
+                        world.systems[system].location.distance(&location)
+                            <= Missions::SEARCH_RADIUS
+                    })
+                    .collect();
+                let hint = *nearby.choose(rng)?;
+                let objective = Objective::Find {
+                    lost,
+                    hint,
+                    radius: Missions::SEARCH_RADIUS,
+                };
+                (objective, hint, 2.)
+            }
+        };
+
+        let distance = paths.distance(target)?;
+        let danger = (world.danger(origin) + world.danger(target)) / 2.;
+        let reward = (Missions::BASE_REWARD + distance * Missions::REWARD_PER_DISTANCE)
+            * difficulty
+            * (1. + 2. * danger);
+        let travel = (distance / Missions::EXPECTED_SPEED).ceil() as u64;
+        Some(Mission {
+            objective,
+            origin,
+            issuer: world.systems[origin].faction,
+            reward: (reward / 10.).round() * 10.,
+            deadline: world.day + Duration(travel + Missions::SLACK_DAYS),
+            status: Status::Offered,
+        })
+    }
+
+    /// Whether the objective of the active mission has been met,
+    /// or `None` if it has failed.
+    fn progress<R: Rng>(world: &World, mission: &Mission, rng: &mut R, days: u64) -> Option<bool> {
+        let player = &world.ships[world.player?];
+        let docked_at = |system| !player.travelling() && player.system == system;
+        match mission.objective {
+            Objective::Deliver {
+                commodity,
+                units,
+                destination,
+            } => Some(docked_at(destination) && player.cargo.get(commodity) >= units),
+            Objective::Survey { center, radius } => {
+                let location = world.systems[center].location;
+                Some(world.systems.iter().enumerate().all(|(id, system)| {
+                    system.location.distance(&location) > radius
+                        || world.knowledge(SystemIndex::from(id)) >= Knowledge::Surveyed
+                }))
+            }
+            Objective::Escort {
+                destination,
+                convoy,
+            } => {
+                let convoy = &world.ships[convoy?];
+                let escorted =
+                    convoy.location.distance(&player.location) <= Mission::ESCORT_DISTANCE;
+                if !convoy.travelling() {
+                    return if convoy.system == destination && escorted {
+                        Some(true)
+                    } else {
+                        None
+                    };
+                }
+                let loss = Missions::LOSS_CHANCE * world.danger(destination) * days as f64;
+                if !escorted && rng.gen::<f64>() < loss {
+                    return None;
+                }
+                Some(false)
+            }
+            Objective::Find { lost, .. } => Some(docked_at(lost)),
+        }
+    }
+
+    /// Complete, fail or expire missions.
+    fn resolve<R: Rng>(world: &mut World, rng: &mut R, days: u64) {
+        let day = world.day;
+        world
+            .missions
+            .retain(|mission| mission.status != Status::Offered || mission.deadline >= day);
+
+        for index in 0..world.missions.len() {
+            let mission = world.missions[index].clone();
+            if mission.status != Status::Active {
+                continue;
+            }
+            let status = match Missions::progress(world, &mission, rng, days) {
+                Some(true) => Status::Completed,
+                Some(false) if day <= mission.deadline => continue,
+                _ => Status::Failed,
+            };
+
+            match (status, &mission.objective) {
+                (
+                    Status::Completed,
+                    &Objective::Deliver {
+                        commodity, units, ..
+                    },
+                ) => {
+                    if let Some(player) = world.player {
+                        world.ships[player].cargo.take(commodity, units);
+                    }
+                }
+                (
+                    Status::Failed,
+                    &Objective::Deliver {
+                        commodity, units, ..
+                    },
+                ) => {
+                    // Goods handed over for a failed delivery are returned.
+                    if let Some(player) = world.player {
+                        world.ships[player].cargo.take(commodity, units);
+                    }
+                }
+                (
+                    Status::Failed,
+                    &Objective::Escort {
+                        convoy: Some(convoy),
+                        ..
+                    },
+                ) => {
+                    // Lost convoys drift no further.
+                    world.ships[convoy].route.clear();
+                }
+                _ => {}
+            }
+            if status == Status::Completed {
+                world.credits += mission.reward;
+            }
+            info!("Mission {:?}: {}", status, mission.describe(world));
+            world.missions[index].status = status;
+        }
+    }
+}
+
+impl Process for Missions {
+    fn name(&self) -> &'static str {
+        "missions"
+    }
+
+    fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64) {
+        Missions::resolve(world, rng, days);
+
+        let graph = self.graph.get_or_insert_with(|| LaneGraph::new(world));
+        let mut offered = vec![0; world.systems.len()];
+        for mission in world
+            .missions
+            .iter()
+            .filter(|mission| mission.status == Status::Offered)
+        {
+            let origin: usize = mission.origin.into();
+            offered[origin] += 1;
+        }
+
+        // Boards are only kept at inhabited systems the player knows of.
+        for (id, &count) in offered.iter().enumerate() {
+            let origin = SystemIndex::from(id);
+            if count >= Missions::BOARD_SIZE
+                || world.systems[origin].population == 0
+                || world.knowledge(origin) < Knowledge::Surveyed
+                || rng.gen::<f64>() >= Missions::OFFER_CHANCE * days as f64
+            {
+                continue;
+            }
+            let paths = graph.paths(origin, Missions::MAX_DISTANCE);
+            if let Some(mission) = Missions::generate(world, &paths, origin, rng) {
+                world.missions.push(mission);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{
+        astronomical::LaneIndex,
+        ship::{Leg, Ship},
+    };
+
+    /// An empty system and an inhabited one joined by a lane, with the player
+    /// docked at the first.
+    fn world() -> World {
+        let mut world = World::new();
+        for &(x, population) in &[(0., 0), (10., 1_000)] {
+            let mut system = System::builder()
+                .location(Point::new(x, 0.))
+                .name("")
+                .build();
+            system.population = population;
+            world.systems.insert(system);
+        }
+        world
+            .lanes
+            .insert(Lane::new(SystemIndex::from(0), SystemIndex::from(1), 10.));
+        let player = Ship::builder()
+            .name("Player")
+            .location(Point::origin())
+            .system(SystemIndex::from(0))
+            .speed(5.)
+            .fuel(100.)
+            .capacity(100.)
+            .build();
+        world.player = Some(world.ships.insert(player));
+        world
+    }
+
+    fn offer(objective: Objective, deadline: u64) -> Mission {
+        Mission {
+            objective,
+            origin: SystemIndex::from(0),
+            issuer: None,
+            reward: 500.,
+            deadline: Date(deadline),
+            status: Status::Offered,
+        }
+    }
+
+    /// Fly the player along the lane to the inhabited system.
+    fn fly(world: &mut World) {
+        let player = world.player.unwrap();
+        world.ships[player].order(vec![Leg::Lane(LaneIndex::from(0))], SystemIndex::from(1));
+    }
+
+    #[test]
+    fn test_deliver() {
+        let mut world = world();
+        let mut rng = Seed::new(0).rng();
+        let player = world.player.unwrap();
+        world.missions.push(offer(
+            Objective::Deliver {
+                commodity: Commodity::Food,
+                units: 10.,
+                destination: SystemIndex::from(1),
+            },
+            100,
+        ));
+        world.accept_mission(0, player).unwrap();
+        world.ships[player].cargo.add(Commodity::Food, 5.);
+
+        // Not there yet.
+        Missions::resolve(&mut world, &mut rng, 1);
+        assert_eq!(world.missions[0].status, Status::Active);
+
+        // Selling keeps the goods of the mission on board.
+        fly(&mut world);
+        world.travel(2.);
+        let (_, earned) = world.deliver(player).unwrap();
+        assert!(earned > 0.);
+        assert_eq!(world.ships[player].cargo.get(Commodity::Food), 10.);
+        let credits = world.credits;
+
+        Missions::resolve(&mut world, &mut rng, 1);
+        assert_eq!(world.missions[0].status, Status::Completed);
+        assert_eq!(world.credits, credits + 500.);
+        assert_eq!(world.ships[player].cargo.get(Commodity::Food), 0.);
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut world = world();
+        let mut rng = Seed::new(0).rng();
+        let player = world.player.unwrap();
+        world.missions.push(offer(
+            Objective::Deliver {
+                commodity: Commodity::Food,
+                units: 10.,
+                destination: SystemIndex::from(1),
+            },
+            5,
+        ));
+        world.missions.push(offer(
+            Objective::Find {
+                lost: SystemIndex::from(1),
+                hint: SystemIndex::from(1),
+                radius: 10.,
+            },
+            20,
+        ));
+        world.accept_mission(0, player).unwrap();
+        assert_eq!(world.ships[player].cargo.get(Commodity::Food), 10.);
+        let credits = world.credits;
+
+        // Overdue missions fail, while stale offers are taken off the board.
+        world.day = Date(10);
+        Missions::resolve(&mut world, &mut rng, 1);
+        assert_eq!(world.missions.len(), 2);
+        world.day = Date(30);
+        Missions::resolve(&mut world, &mut rng, 1);
+        assert_eq!(world.missions.len(), 1);
+        assert_eq!(world.missions[0].status, Status::Failed);
+        assert_eq!(world.credits, credits);
+        assert_eq!(world.ships[player].cargo.get(Commodity::Food), 0.);
+    }
+
+    #[test]
+    fn test_escort() {
+        let escort = |follow: bool| {
+            let mut world = world();
+            let mut rng = Seed::new(0).rng();
+            let player = world.player.unwrap();
+            world.missions.push(offer(
+                Objective::Escort {
+                    destination: SystemIndex::from(1),
+                    convoy: None,
+                },
+                100,
+            ));
+            world.accept_mission(0, player).unwrap();
+            if follow {
+                fly(&mut world);
+            }
+            for _ in 0..3 {
+                world.travel(1.);
+                Missions::resolve(&mut world, &mut rng, 1);
+            }
+            (world.missions[0].status, world.credits)
+        };
+
+        // The convoy only counts as delivered if the player arrives with it.
+        let (status, credits) = escort(true);
+        assert_eq!(status, Status::Completed);
+        assert_eq!(credits, world().credits + 500.);
+        assert_eq!(escort(false).0, Status::Failed);
+    }
+}
@@ -1,79 +1,79 @@
 use crate::{
+    calendar::{Date, Duration, DAYS_PER_YEAR},
     config::Config,
-    entity::{astronomical::System, EntityArray},
+    entity::{
+        astronomical::{Body, BodyKind, Lane, StarClass, System, SystemIndex},
+        faction::{Faction, FactionIndex, Government},
+        EntityArray,
+    },
+    graph::LaneGraph,
+    history::EventKind,
     point::Point,
+    spatial::SpatialIndex,
     world::World,
 };
 pub use log::{debug, info, warn};
-use rand::{
-    distributions::{Distribution, Normal},
-    SeedableRng,
-};
 use rand_chacha::ChaChaRng;
 use std::time::Instant;
 
+mod charts;
+mod deposits;
+mod diplomacy;
+mod expansion;
+mod factions;
+mod fleets;
+mod history;
+mod incidents;
+mod lanes;
+mod markets;
+mod mining;
+mod missions;
 mod namegen;
+mod naming;
+mod pipeline;
+mod placement;
+mod planets;
+mod player;
+mod population;
+mod seed;
+mod settlement;
+mod simulation;
+mod stars;
+mod trade;
+use charts::Charts;
+use deposits::Deposits;
+use diplomacy::Diplomacy;
+use expansion::{strengths, Expansion};
+use factions::Factions;
+use fleets::Fleets;
+use history::History;
+use incidents::Incidents;
+use lanes::Lanes;
+use markets::Markets;
+use mining::Mining;
+use missions::Missions;
 use namegen::NameGen;
+use naming::Naming;
+pub use pipeline::{stage, Pipeline, Stage};
+use placement::Placement;
+use planets::Planets;
+use player::Player;
+use population::PopulationGrowth;
+pub use seed::Seed;
+use settlement::Settlement;
+pub use simulation::{Process, Simulation};
+use stars::Stars;
+use trade::Trade;
 
-/// Generate an initial world.
-pub fn generate(config: &Config, world: &mut World) {
-    info!("Generating the initial world");
-
-    let mut rng = ChaChaRng::seed_from_u64(config.simulation.map_seed.into());
-
-    // Measure time for generation.
-    let now = Instant::now();
-
-    // Clusters are spaced uniformly, systems gaussian.
-    let loc_x = Normal::new(0., config.simulation.system_spread);
-    let loc_y = Normal::new(0., config.simulation.system_spread);
-
-    // Generate system locations.
-    let mut locations = vec![];
-    for _ in 0..config.simulation.number_of_systems {
-        locations.push(Point::new(
-            loc_x.sample::<ChaChaRng>(&mut rng),
-            loc_y.sample::<ChaChaRng>(&mut rng),
-        ))
-    }
-
-    // Generate actual systems.
-    let mut systems = EntityArray::new();
-    let mut system_ids = vec![];
-    for loc in locations {
-        let system = System::builder().location(loc).name("").build();
-        let id = systems.insert(system);
-        system_ids.push(id);
-    }
-
-    world.systems = systems;
-
-    info!(
-        "Generated {} systems, taking {} ms",
-        system_ids.len(),
-        ((now.elapsed().as_secs() * 1_000) + u64::from(now.elapsed().subsec_millis()))
-    );
-}
-
-/// Simulate the world state for a the configured time.
-pub fn simulate(_config: &Config, _world: &mut World) {
-    // TODO: Do some implementation.
-}
-
-/// Finalize the generation/simulation of the world.
-pub fn finalize(config: &Config, world: &mut World) {
-    // Assign system names.
-    let mut rng = ChaChaRng::seed_from_u64(config.simulation.map_seed.into());
-    let mut sng = NameGen::new();
-    for name in include_str!("../../resources/eso.txt").lines() {
-        sng.train(name);
-    }
-    for system in &mut world.systems {
-        if let Ok(name) = sng.generate(&mut rng) {
-            system.name = name;
-        } else {
-            warn!("Ran out of names to assign to systems");
-            break;
-        }
-    }
+/// Simulation of the world while the game is played, advancing a day at a time.
+pub fn live(config: &Config) -> Simulation {
+    let seed = Seed::new(config.simulation.map_seed.into()).derive("live");
+    let mut simulation = Simulation::new(seed, 1);
+    simulation.register(Box::new(PopulationGrowth::new(config)));
+    simulation.register(Box::new(Markets));
+    simulation.register(Box::new(Trade::new()));
+    simulation.register(Box::new(Mining::new(config)));
+    simulation.register(Box::new(Missions::new()));
+    simulation.register(Box::new(Fleets::new()));
+    simulation
 }
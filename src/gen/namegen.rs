@@ -1,12 +1,16 @@
 use super::*;
-use markov::Chain;
 use rand::Rng;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+
+/// Words following each word of the training data, or the start of a name,
+/// along with how often. `None` marks the start or end of a name.
+/// Ordered, so that generated names only depend on the random stream.
+type Chain = BTreeMap<Option<String>, BTreeMap<Option<String>, usize>>;
 
 /// Name generator which generates based on names given in training data.
 pub struct NameGen {
     cache: HashSet<String>,
-    chain: Chain<String>,
+    chain: Chain,
 }
 
 impl NameGen {
@@ -23,14 +27,52 @@ impl NameGen {
 
     /// Train the underlying model using the given name.
     pub fn train(&mut self, name: &str) {
-        self.chain.feed_str(name);
+        let mut previous = None;
+        for word in name.split(' ').map(|word| Some(word.to_string())) {
+            *self
+                .chain
+                .entry(previous)
+                .or_default()
+                .entry(word.clone())
+                .or_default() += 1;
+            previous = word;
+        }
+        *self
+            .chain
+            .entry(previous)
+            .or_default()
+            .entry(None)
+            .or_default() += 1;
+    }
+
+    /// Walk the chain from the start until reaching the end of a name.
+    fn walk<R: Rng>(&self, rng: &mut R) -> Option<String> {
+        let mut words = vec![];
+        let mut current = None;
+        loop {
+            let next = self.chain.get(&current)?;
+            let mut pick = rng.gen_range(0, next.values().sum::<usize>());
+            current = next
+                .iter()
+                .find(|(_, &count)| {
+                    let found = pick < count;
+                    pick = pick.saturating_sub(count);
+                    found
+                })?
+                .0
+                .clone();
+            match current {
+                Some(ref word) => words.push(word.clone()),
+                None => return Some(words.join(" ")),
+            }
+        }
     }
 
     /// Generate a new name.
     /// Attempt to generate a new unique name, running a maximum number of tries before returning none.
-    pub fn generate<R: Rng>(&mut self, _gen: &mut R) -> Result<String, ()> {
+    pub fn generate<R: Rng>(&mut self, rng: &mut R) -> Result<String, ()> {
         for _ in 0..Self::MAX_TRIES {
-            let name = self.chain.generate_str();
+            let name = self.walk(rng).ok_or(())?;
             if !self.cache.contains(&name) {
                 self.cache.insert(name.clone());
                 return Ok(name);
@@ -47,9 +89,23 @@ mod tests {
     fn test_namegen() {
         let data = "elizabeth";
         let mut gen = NameGen::new();
-        let mut rng = ChaChaRng::seed_from_u64(42);
+        let mut rng = Seed::new(42).rng();
         gen.train(data);
         assert_eq!(gen.generate(&mut rng), Ok(String::from(data)));
         assert!(gen.generate(&mut rng).is_err());
     }
+
+    #[test]
+    fn test_reproducible() {
+        let names = |seed| {
+            let mut gen = NameGen::new();
+            for name in &["andromeda", "antares", "aldebaran", "altair", "arcturus"] {
+                gen.train(name);
+            }
+            let mut rng = Seed::new(seed).rng();
+            (0..10).map(|_| gen.generate(&mut rng)).collect::<Vec<_>>()
+        };
+        assert_eq!(names(7), names(7));
+        assert_ne!(names(7), names(8));
+    }
 }
@@ -0,0 +1,39 @@
+use super::*;
+
+/// Assigns generated names to systems, and names factions after their home.
+pub struct Naming;
+
+impl Stage for Naming {
+    fn name(&self) -> &'static str {
+        "naming"
+    }
+
+    fn run(&self, _: &Config, world: &mut World, seed: Seed) {
+        let mut rng = seed.rng();
+        let mut sng = NameGen::new();
+        for name in include_str!("../../resources/eso.txt").lines() {
+            sng.train(name);
+        }
+        // Settled systems are named first, in case we run out of names.
+        let mut order: Vec<SystemIndex> = (0..world.systems.len()).map(SystemIndex::from).collect();
+        order.sort_by_key(|&id| world.systems[id].population == 0);
+        for id in order {
+            if let Ok(name) = sng.generate(&mut rng) {
+                world.systems[id].name = name;
+            } else {
+                warn!("Ran out of names to assign to systems");
+                break;
+            }
+        }
+
+        for faction in world.factions.iter_mut() {
+            let home = &world.systems[faction.home].name;
+            let mut chars = home.chars();
+            let home: String = chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default();
+            faction.name = format!("{} {}", home, faction.government.title());
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use super::*;
+
+use failure::{format_err, Error};
+
+/// A single named step of world generation.
+pub trait Stage {
+    /// Name used to refer to the stage from the config.
+    fn name(&self) -> &'static str;
+
+    /// Run the stage, updating the world in place.
+    /// Random draws should only be made from streams derived from the given seed.
+    fn run(&self, config: &Config, world: &mut World, seed: Seed);
+}
+
+/// Look up a stage by name.
+pub fn stage(name: &str) -> Option<Box<dyn Stage>> {
+    match name {
+        "placement" => Some(Box::new(Placement)),
+        "stars" => Some(Box::new(Stars)),
+        "planets" => Some(Box::new(Planets)),
+        "deposits" => Some(Box::new(Deposits)),
+        "lanes" => Some(Box::new(Lanes)),
+        "settlement" => Some(Box::new(Settlement)),
+        "factions" => Some(Box::new(Factions)),
+        "naming" => Some(Box::new(Naming)),
+        "history" => Some(Box::new(History)),
+        "player" => Some(Box::new(Player)),
+        "charts" => Some(Box::new(Charts)),
+        _ => None,
+    }
+}
+
+/// Ordered list of generation stages.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn Stage>>,
+}
+
+impl Pipeline {
+    /// Create a new empty pipeline.
+    pub fn new() -> Self {
+        Pipeline::default()
+    }
+
+    /// Create a pipeline of the stages listed in the config.
+    pub fn from_config(config: &Config) -> Result<Self, Error> {
+        Pipeline::from_names(&config.generation.stages)
+    }
+
+    /// Create a pipeline of the given stages, in order.
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Self, Error> {
+        let mut pipeline = Pipeline::new();
+        for name in names {
+            let name = name.as_ref();
+            let stage = stage(name).ok_or_else(|| format_err!("Unknown stage: {}", name))?;
+            pipeline.add(stage);
+        }
+        Ok(pipeline)
+    }
+
+    /// Append a stage to the end of the pipeline.
+    pub fn add(&mut self, stage: Box<dyn Stage>) {
+        self.stages.push(stage);
+    }
+
+    /// Names of the stages, in order.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+
+    /// Run all stages in order on the world.
+    /// Each stage is seeded independently of which other stages are run.
+    pub fn run(&self, config: &Config, world: &mut World) {
+        let root = Seed::new(config.simulation.map_seed.into());
+        for stage in &self.stages {
+            info!("Running generation stage: {}", stage.name());
+            let now = Instant::now();
+
+            stage.run(config, world, root.derive(stage.name()));
+
+            info!(
+                "Finished stage {}, taking {} ms",
+                stage.name(),
+                ((now.elapsed().as_secs() * 1_000) + u64::from(now.elapsed().subsec_millis()))
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::Knowledge;
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Records the seed it is run with.
+    struct Probe {
+        name: &'static str,
+        seeds: Rc<RefCell<Vec<Seed>>>,
+    }
+    impl Stage for Probe {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn run(&self, _: &Config, _: &mut World, seed: Seed) {
+            self.seeds.borrow_mut().push(seed);
+        }
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let mut config: Config = toml::from_str(include_str!("../../Config.toml")).unwrap();
+        config.simulation.number_of_systems = 200;
+        config.simulation.years = 1;
+
+        let mut world = World::new();
+        Pipeline::from_config(&config)
+            .unwrap()
+            .run(&config, &mut world);
+        assert_eq!(world.systems.len(), 200);
+        assert!(world.systems.iter().all(|system| !system.name.is_empty()));
+
+        // Factions and the systems they own agree on ownership.
+        assert!(world.factions.len() > 0);
+        for (id, faction) in world.factions.iter().enumerate() {
+            assert!(faction.name.ends_with(faction.government.title()));
+            for &system in &faction.systems {
+                assert_eq!(world.systems[system].faction, Some(FactionIndex::from(id)));
+            }
+        }
+
+        // The player starts docked at a system.
+        let player = &world.ships[world.player.unwrap()];
+        assert_eq!(player.location, world.systems[player.system].location);
+        assert!(!player.travelling());
+        assert_eq!(world.knowledge(player.system), Knowledge::Visited);
+
+        // Only re-running naming keeps everything else.
+        let lanes = world.lanes.len();
+        let bodies = world.bodies.len();
+        let locations: Vec<_> = world.systems.iter().map(|s| s.location).collect();
+        Pipeline::from_names(&["naming"])
+            .unwrap()
+            .run(&config, &mut world);
+        assert_eq!(world.lanes.len(), lanes);
+        assert_eq!(world.bodies.len(), bodies);
+        assert!(world
+            .systems
+            .iter()
+            .zip(locations)
+            .all(|(system, location)| system.location == location && !system.name.is_empty()));
+
+        assert!(Pipeline::from_names(&["placement", "terraforming"]).is_err());
+    }
+
+    #[test]
+    fn test_independent_stages() {
+        let mut config: Config = toml::from_str(include_str!("../../Config.toml")).unwrap();
+        config.simulation.number_of_systems = 200;
+
+        let mut full = World::new();
+        Pipeline::from_names(&["placement", "stars", "planets"])
+            .unwrap()
+            .run(&config, &mut full);
+
+        // Skipping planets must not shift system locations or star classes.
+        let mut partial = World::new();
+        Pipeline::from_names(&["placement", "stars"])
+            .unwrap()
+            .run(&config, &mut partial);
+        assert!(full
+            .systems
+            .iter()
+            .zip(partial.systems.iter())
+            .all(|(a, b)| a.location == b.location && a.star == b.star));
+
+        // Nor does generating more systems move the existing ones.
+        config.simulation.number_of_systems = 300;
+        let mut more = World::new();
+        Pipeline::from_names(&["placement"])
+            .unwrap()
+            .run(&config, &mut more);
+        assert!(full
+            .systems
+            .iter()
+            .zip(more.systems.iter())
+            .all(|(a, b)| a.location == b.location));
+    }
+
+    #[test]
+    fn test_stage_seeds() {
+        let config: Config = toml::from_str(include_str!("../../Config.toml")).unwrap();
+        let seeds = Rc::new(RefCell::new(vec![]));
+        let probe = |name| {
+            Box::new(Probe {
+                name,
+                seeds: seeds.clone(),
+            })
+        };
+
+        let mut pipeline = Pipeline::new();
+        pipeline.add(probe("first"));
+        pipeline.add(probe("second"));
+        pipeline.run(&config, &mut World::new());
+        let mut alone = Pipeline::new();
+        alone.add(probe("second"));
+        alone.run(&config, &mut World::new());
+
+        // Each stage gets its own seed, whichever stages run before it.
+        let seeds = seeds.borrow();
+        assert_ne!(seeds[0], seeds[1]);
+        assert_eq!(seeds[1], seeds[2]);
+    }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+use rand::distributions::{Distribution, Normal};
+
+/// Places systems in a gaussian spread around the galactic center.
+pub struct Placement;
+
+impl Stage for Placement {
+    fn name(&self) -> &'static str {
+        "placement"
+    }
+
+    fn run(&self, config: &Config, world: &mut World, seed: Seed) {
+        let loc_x = Normal::new(0., config.simulation.system_spread);
+        let loc_y = Normal::new(0., config.simulation.system_spread);
+
+        // Everything else refers to systems, so start from scratch.
+        *world = World::new();
+        for id in 0..config.simulation.number_of_systems as usize {
+            let mut rng = seed.index(id).rng();
+            let location = Point::new(
+                loc_x.sample::<ChaChaRng>(&mut rng),
+                loc_y.sample::<ChaChaRng>(&mut rng),
+            );
+            world
+                .systems
+                .insert(System::builder().location(location).name("").build());
+        }
+        info!("Placed {} systems", world.systems.len());
+    }
+}
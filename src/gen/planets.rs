@@ -0,0 +1,58 @@
+use super::*;
+
+use rand::Rng;
+
+/// Generates planets and belts orbiting each star.
+pub struct Planets;
+
+impl Planets {
+    /// Maximum number of bodies orbiting a single star.
+    const MAX_BODIES: usize = 8;
+}
+
+impl Stage for Planets {
+    fn name(&self) -> &'static str {
+        "planets"
+    }
+
+    fn run(&self, _: &Config, world: &mut World, seed: Seed) {
+        world.bodies = EntityArray::new();
+        for (id, system) in world.systems.iter_mut().enumerate() {
+            let mut rng = seed.index(id).rng();
+
+            // Habitable zone scales with the square root of luminosity.
+            let habitable = system.star.luminosity().sqrt();
+            let count = rng.gen_range(0, Planets::MAX_BODIES + 1);
+
+            system.bodies.clear();
+            let mut orbit = rng.gen_range(0.2, 0.6);
+            for _ in 0..count {
+                let kind = if rng.gen::<f64>() < 0.15 {
+                    BodyKind::Belt
+                } else if orbit < habitable * 0.8 {
+                    BodyKind::Rocky
+                } else if orbit < habitable * 1.5 {
+                    if rng.gen() {
+                        BodyKind::Ocean
+                    } else {
+                        BodyKind::Rocky
+                    }
+                } else if orbit < habitable * 10. {
+                    BodyKind::GasGiant
+                } else {
+                    BodyKind::Ice
+                };
+                let body = Body::builder()
+                    .system(SystemIndex::from(id))
+                    .kind(kind)
+                    .orbit(orbit)
+                    .build();
+                system.bodies.push(world.bodies.insert(body));
+
+                // Roughly geometric spacing between orbits.
+                orbit *= rng.gen_range(1.4, 2.2);
+            }
+        }
+        info!("Generated {} bodies", world.bodies.len());
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+use crate::{
+    entity::ship::Ship,
+    knowledge::Observer,
+    outfitting::{Hull, Module, ModuleKind, Outfit},
+};
+
+/// Creates the player's ship, docked at the most populous system.
+pub struct Player;
+
+impl Player {
+    /// Credits held by the player at the start.
+    const CREDITS: f64 = 1_000.;
+
+    /// Hull of the player's ship, with room to grow beyond the starting modules.
+    fn hull() -> Hull {
+        use self::ModuleKind::*;
+        Hull {
+            mass: 40.,
+            max_mass: 200.,
+            power: 20.,
+            slots: vec![
+                Drive, FuelTank, FuelTank, FuelTank, CargoHold, CargoHold, CargoHold, Scanner,
+                Weapon, Weapon, Shield,
+            ],
+        }
+    }
+
+    /// Outfit of the player's ship at the start, basic modules in most slots
+    /// but nothing for fighting.
+    fn outfit() -> Outfit {
+        use self::ModuleKind::*;
+        let basic = |kind| Some(Module::new(kind, 1));
+        Outfit::new(
+            Player::hull(),
+            vec![
+                basic(Drive),
+                basic(FuelTank),
+                basic(FuelTank),
+                None,
+                basic(CargoHold),
+                basic(CargoHold),
+                None,
+                basic(Scanner),
+                None,
+                None,
+                None,
+            ],
+        )
+    }
+}
+
+impl Stage for Player {
+    fn name(&self) -> &'static str {
+        "player"
+    }
+
+    fn run(&self, _: &Config, world: &mut World, _: Seed) {
+        world.ships = EntityArray::new();
+        world.player = None;
+        world.surveys.clear();
+        world.claims.clear();
+        world.operations.clear();
+        world.credits = Player::CREDITS;
+
+        let start = match (0..world.systems.len())
+            .map(SystemIndex::from)
+            .max_by_key(|&id| world.systems[id].population)
+        {
+            Some(start) => start,
+            None => return warn!("No system to start the player at"),
+        };
+        let mut ship = Ship::builder()
+            .name("Prospector")
+            .location(world.systems[start].location)
+            .system(start)
+            .speed(0.)
+            .fuel(f64::INFINITY)
+            .observer(Some(Observer::Player))
+            .build();
+        // Stats derive from the outfit, starting with a full tank.
+        ship.refit(Player::outfit());
+        world.player = Some(world.ships.insert(ship));
+        info!("Player starts at {}", world.systems[start].name);
+    }
+}
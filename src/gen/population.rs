@@ -0,0 +1,41 @@
+use super::*;
+
+use rand::Rng;
+
+/// Logistic population growth towards each system's carrying capacity.
+pub struct PopulationGrowth {
+    rate: f64,
+    capacity: f64,
+}
+
+impl PopulationGrowth {
+    /// Maximum relative deviation from the growth rate in a single step.
+    const VARIANCE: f64 = 0.5;
+
+    /// Create a new population growth process using the configured rates.
+    pub fn new(config: &Config) -> Self {
+        PopulationGrowth {
+            rate: config.simulation.population_growth,
+            capacity: config.simulation.population_capacity,
+        }
+    }
+}
+
+impl Process for PopulationGrowth {
+    fn name(&self) -> &'static str {
+        "population growth"
+    }
+
+    fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64) {
+        let years = days as f64 / DAYS_PER_YEAR as f64;
+        for system in &mut world.systems {
+            if system.population == 0 {
+                continue;
+            }
+            let population = system.population as f64;
+            let rate = self.rate * (1. + rng.gen_range(-Self::VARIANCE, Self::VARIANCE));
+            let growth = rate * population * (1. - population / self.capacity) * years;
+            system.population = (population + growth).max(0.) as u64;
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+/// Node in a hierarchy of seeds, from which stable sub-seeds are derived for
+/// each stage and entity. Random draws using one seed never shift the results
+/// of another, so e.g. changing planet generation keeps system positions intact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Seed(u64);
+
+impl Seed {
+    /// Create the root seed, usually from the configured map seed.
+    pub fn new(seed: u64) -> Self {
+        Seed(mix(seed))
+    }
+
+    /// Derive the sub-seed for the part with the given label, e.g. a stage.
+    pub fn derive(self, label: &str) -> Self {
+        // FNV-1a, which unlike the std hashers is guaranteed to be stable.
+        let hash = label.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        self.combine(hash)
+    }
+
+    /// Derive the sub-seed for the entity with the given index.
+    pub fn index<I: Into<usize>>(self, index: I) -> Self {
+        self.combine(index.into() as u64)
+    }
+
+    /// Create a random number generator from the seed.
+    pub fn rng(self) -> ChaChaRng {
+        ChaChaRng::seed_from_u64(self.0)
+    }
+
+    fn combine(self, value: u64) -> Self {
+        Seed(mix(self.0.rotate_left(17) ^ mix(value)))
+    }
+}
+
+/// SplitMix64 finalizer, spreading similar inputs over all bits.
+fn mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_derive() {
+        let root = Seed::new(42);
+        assert_eq!(root.derive("stars"), Seed::new(42).derive("stars"));
+        assert_ne!(root.derive("stars"), root.derive("planets"));
+        assert_ne!(root.index(1usize), root.index(2usize));
+        assert_ne!(
+            root.derive("stars").derive("planets"),
+            root.derive("planets").derive("stars")
+        );
+        assert_ne!(
+            root.rng().gen::<u64>(),
+            root.index(0usize).rng().gen::<u64>()
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+use rand::Rng;
+
+/// Settles a few systems, from which the rest of the galaxy is colonised.
+pub struct Settlement;
+
+impl Stage for Settlement {
+    fn name(&self) -> &'static str {
+        "settlement"
+    }
+
+    fn run(&self, config: &Config, world: &mut World, seed: Seed) {
+        for (id, system) in world.systems.iter_mut().enumerate() {
+            let mut rng = seed.index(id).rng();
+            system.population = if rng.gen::<f64>() < config.simulation.settled_fraction {
+                rng.gen_range(1_000, 1_000_000)
+            } else {
+                0
+            };
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+/// Process updating some part of the world on each simulation step,
+/// e.g. population growth or the economy.
+pub trait Process {
+    /// Name of the process, used for logging.
+    fn name(&self) -> &'static str;
+
+    /// Advance the process the given number of days.
+    fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64);
+}
+
+/// Deterministic fixed step simulation driver.
+/// Registered processes are run in order of registration on each step,
+/// each drawing from its own random stream derived from the seed and the day,
+/// so that a simulation resumed from a save continues the same way.
+pub struct Simulation {
+    processes: Vec<Box<dyn Process>>,
+    seed: Seed,
+    step_days: u64,
+}
+
+impl Simulation {
+    /// Create a new simulation seeded with the given seed, advancing the given
+    /// number of days on each step.
+    /// Panics if the step is zero days long.
+    pub fn new(seed: Seed, step_days: u64) -> Self {
+        assert!(
+            step_days > 0,
+            "Simulation steps must be at least a day long"
+        );
+        Simulation {
+            processes: vec![],
+            seed,
+            step_days,
+        }
+    }
+
+    /// Register a process to be run on each step, after all previously registered processes.
+    pub fn register(&mut self, process: Box<dyn Process>) {
+        debug!("Registering simulation process {}", process.name());
+        self.processes.push(process);
+    }
+
+    /// Advance the world one step.
+    pub fn step(&mut self, world: &mut World) {
+        let days = self.step_days;
+        self.advance(world, days);
+    }
+
+    /// Advance the world the given number of days, with the last step
+    /// shortened to not overshoot.
+    pub fn run(&mut self, world: &mut World, days: u64) {
+        let steps = days.div_ceil(self.step_days);
+        let now = Instant::now();
+        let mut remaining = days;
+        for step in 0..steps {
+            let days = remaining.min(self.step_days);
+            self.advance(world, days);
+            remaining -= days;
+
+            // Report progress every tenth of the way.
+            if (step + 1) % (steps / 10).max(1) == 0 {
+                info!(
+                    "Simulated {} of {} steps, {} ms elapsed",
+                    step + 1,
+                    steps,
+                    ((now.elapsed().as_secs() * 1_000) + u64::from(now.elapsed().subsec_millis()))
+                );
+            }
+        }
+    }
+
+    /// Run every process over the given number of days.
+    fn advance(&mut self, world: &mut World, days: u64) {
+        for process in &mut self.processes {
+            let mut rng = self
+                .seed
+                .derive(process.name())
+                .index(world.day.0 as usize)
+                .rng();
+            process.step(world, &mut rng, days);
+        }
+        world.day += Duration(days);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    /// Appends a random number to each system name on every step.
+    struct Scribble {}
+    impl Process for Scribble {
+        fn name(&self) -> &'static str {
+            "scribble"
+        }
+
+        fn step(&mut self, world: &mut World, rng: &mut ChaChaRng, days: u64) {
+            for system in &mut world.systems {
+                system
+                    .name
+                    .push_str(&format!("{}-{};", days, rng.gen::<u8>()));
+            }
+        }
+    }
+
+    fn simulation(seed: Seed) -> Simulation {
+        let mut simulation = Simulation::new(seed, 30);
+        simulation.register(Box::new(Scribble {}));
+        simulation
+    }
+
+    fn run(seed: Seed) -> World {
+        let mut world = World::new();
+        world
+            .systems
+            .insert(System::builder().location(Point::origin()).name("").build());
+        simulation(seed).run(&mut world, 365);
+        world
+    }
+
+    #[test]
+    fn test_simulation() {
+        let world = run(Seed::new(42));
+        assert_eq!(world.day, Date(365));
+        let name = &world.systems.iter().next().unwrap().name;
+        assert_eq!(name.matches(';').count(), 13);
+        // The last step only covers the days left over.
+        assert!(name.rsplit(';').nth(1).unwrap().starts_with("5-"));
+
+        // Same seed gives the same result.
+        assert_eq!(
+            world.systems.iter().next().unwrap().name,
+            run(Seed::new(42)).systems.iter().next().unwrap().name
+        );
+    }
+
+    #[test]
+    fn test_resume() {
+        // A fresh simulation picks up where another left off, as after loading a save.
+        let mut world = World::new();
+        world
+            .systems
+            .insert(System::builder().location(Point::origin()).name("").build());
+        simulation(Seed::new(42)).run(&mut world, 180);
+        simulation(Seed::new(42)).run(&mut world, 185);
+        assert_eq!(
+            world.systems.iter().next().unwrap().name,
+            run(Seed::new(42)).systems.iter().next().unwrap().name
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_step() {
+        Simulation::new(Seed::new(42), 0);
+    }
+}
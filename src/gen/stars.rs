@@ -0,0 +1,26 @@
+use super::*;
+
+use rand::Rng;
+
+/// Assigns a spectral class to the star of each system.
+pub struct Stars;
+
+impl Stage for Stars {
+    fn name(&self) -> &'static str {
+        "stars"
+    }
+
+    fn run(&self, _: &Config, world: &mut World, seed: Seed) {
+        for (id, system) in world.systems.iter_mut().enumerate() {
+            let mut roll = seed.index(id).rng().gen::<f64>();
+            system.star = StarClass::ALL
+                .iter()
+                .cloned()
+                .find(|class| {
+                    roll -= class.frequency();
+                    roll < 0.
+                })
+                .unwrap_or_default();
+        }
+    }
+}
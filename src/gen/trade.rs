@@ -0,0 +1,169 @@
+use super::*;
+
+use crate::{
+    economy::{Commodity, Shipment},
+    graph::Paths,
+};
+use std::collections::HashMap;
+
+/// NPC traders moving surplus goods along lanes to where they sell for the
+/// most, evening out prices between markets.
+#[derive(Default)]
+pub struct Trade {
+    graph: Option<LaneGraph>,
+    paths: HashMap<SystemIndex, Paths>,
+}
+
+impl Trade {
+    /// Maximum travel distance of a trade route.
+    const MAX_DISTANCE: f64 = 60.;
+    /// Transport cost per unit of goods and unit of distance.
+    const TRANSPORT_COST: f64 = 0.1;
+    /// Distance travelled by traders per day.
+    const SPEED: f64 = 2.;
+    /// Units shipped per thousand inhabitants and day.
+    const CAPACITY: f64 = 0.05;
+    /// Fraction of the surplus shipped at once.
+    const SHARE: f64 = 0.5;
+    /// Smallest population able to support traders of its own.
+    const MIN_POPULATION: u64 = 100_000;
+
+    /// Create a new trade process.
+    pub fn new() -> Self {
+        Trade::default()
+    }
+
+    /// Move shipments in transit, delivering those which arrive.
+    fn travel(world: &mut World, days: u64) {
+        let distance = Trade::SPEED * days as f64;
+        for mut shipment in ::std::mem::take(&mut world.shipments) {
+            shipment.progress += distance;
+            while shipment.leg < shipment.route.len() {
+                let lane = &mut world.lanes[shipment.route[shipment.leg]];
+                if shipment.progress < lane.length {
+                    break;
+                }
+                shipment.progress -= lane.length;
+                lane.volume += shipment.units;
+                shipment.leg += 1;
+            }
+
+            if shipment.leg == shipment.route.len() {
+                world.systems[shipment.destination]
+                    .market
+                    .good_mut(shipment.commodity)
+                    .stock += shipment.units;
+            } else {
+                world.shipments.push(shipment);
+            }
+        }
+    }
+}
+
+impl Process for Trade {
+    fn name(&self) -> &'static str {
+        "trade"
+    }
+
+    fn step(&mut self, world: &mut World, _: &mut ChaChaRng, days: u64) {
+        Trade::travel(world, days);
+
+        let graph = self.graph.get_or_insert_with(|| LaneGraph::new(world));
+        for source in 0..world.systems.len() {
+            let source = SystemIndex::from(source);
+            if world.systems[source].population < Trade::MIN_POPULATION {
+                continue;
+            }
+            let paths = self
+                .paths
+                .entry(source)
+                .or_insert_with(|| graph.paths(source, Trade::MAX_DISTANCE));
+
+            // Pick the most profitable commodity and destination.
+            let market = &world.systems[source].market;
+            let mut best = None;
+            let mut best_profit = 0.;
+            // Sorted, so that ties go to the same destination on every run.
+            let mut destinations: Vec<(SystemIndex, f64)> = paths.systems().collect();
+            destinations.sort_by_key(|&(system, _)| Into::<usize>::into(system));
+            for (destination, distance) in destinations {
+                let other = &world.systems[destination];
+                if destination == source || other.population == 0 {
+                    continue;
+                }
+                for &commodity in Commodity::ALL.iter() {
+                    if market.good(commodity).stock <= market.demand(commodity) {
+                        continue;
+                    }
+                    let profit = other.market.price(commodity)
+                        - market.price(commodity)
+                        - distance * Trade::TRANSPORT_COST;
+                    if profit > best_profit {
+                        best_profit = profit;
+                        best = Some((commodity, destination));
+                    }
+                }
+            }
+
+            if let Some((commodity, destination)) = best {
+                let system = &mut world.systems[source];
+                let capacity = system.population as f64 / 1_000. * Trade::CAPACITY * days as f64;
+                let surplus = system.market.good(commodity).stock - system.market.demand(commodity);
+                let units = (surplus * Trade::SHARE).min(capacity);
+                system.market.good_mut(commodity).stock -= units;
+
+                world.shipments.push(Shipment {
+                    commodity,
+                    units,
+                    destination,
+                    route: paths.lanes(destination).unwrap_or_default(),
+                    leg: 0,
+                    progress: 0.,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade() {
+        // Two settled systems connected through an empty one.
+        let mut world = World::new();
+        for x in &[0., 10., 20.] {
+            let mut system = System::builder()
+                .location(Point::new(*x, 0.))
+                .name("")
+                .build();
+            system.population = 1_000_000;
+            world.systems.insert(system);
+        }
+        world.systems[SystemIndex::from(1)].population = 0;
+        for (from, to) in &[(0, 1), (1, 2)] {
+            let (from, to) = (SystemIndex::from(*from), SystemIndex::from(*to));
+            world.lanes.insert(Lane::new(from, to, 10.));
+        }
+
+        // Food is plentiful at the first system and scarce at the last.
+        let (rich, poor) = (SystemIndex::from(0), SystemIndex::from(2));
+        world.systems[rich].market.good_mut(Commodity::Food).stock = 10_000.;
+        world.systems[poor].market.good_mut(Commodity::Food).price = 50.;
+
+        let mut rng = Seed::new(0).rng();
+        let mut trade = Trade::new();
+        trade.step(&mut world, &mut rng, 5);
+        assert_eq!(world.shipments.len(), 1);
+        assert_eq!(world.shipments[0].destination, poor);
+        assert!(world.systems[rich].market.good(Commodity::Food).stock < 10_000.);
+
+        // Arrives after travelling both lanes.
+        for _ in 0..2 {
+            trade.step(&mut world, &mut rng, 5);
+        }
+        assert!(world.systems[poor].market.good(Commodity::Food).stock > 0.);
+        assert!(world.lanes.iter().all(|lane| lane.volume > 0.));
+    }
+}
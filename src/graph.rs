@@ -0,0 +1,428 @@
+use crate::{
+    entity::{
+        astronomical::{LaneIndex, SystemIndex},
+        ship::{Leg, Ship},
+    },
+    spatial::SpatialIndex,
+    world::World,
+};
+use failure::{format_err, Error};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// Systems connected by hyperlanes, for finding paths along lanes and
+/// planning routes which also jump between systems.
+pub struct LaneGraph {
+    adjacency: Vec<Vec<(SystemIndex, LaneIndex)>>,
+    lengths: Vec<f64>,
+    /// Systems by location, for finding those within jump range.
+    index: SpatialIndex,
+}
+
+/// Partial route found while planning, see `LaneGraph::cheapest`.
+struct Label {
+    system: SystemIndex,
+    /// Fuel left on arrival, after any refuelling.
+    fuel: f64,
+    /// Label of the previous system and the leg taken from it.
+    previous: Option<(usize, Leg)>,
+}
+
+impl LaneGraph {
+    /// Cell size in world units used for the spatial index.
+    const INDEX_CELL_SIZE: f64 = 10.;
+
+    /// Create a new graph from the lanes of the world.
+    pub fn new(world: &World) -> Self {
+        let mut adjacency = vec![vec![]; world.systems.len()];
+        let mut lengths = vec![];
+        for (id, lane) in world.lanes.iter().enumerate() {
+            let from: usize = lane.from.into();
+            let to: usize = lane.to.into();
+            adjacency[from].push((lane.to, LaneIndex::from(id)));
+            adjacency[to].push((lane.from, LaneIndex::from(id)));
+            lengths.push(lane.length);
+        }
+        let mut index = SpatialIndex::new(Self::INDEX_CELL_SIZE);
+        for (id, system) in world.systems.iter().enumerate() {
+            index.insert(system.location, id);
+        }
+        LaneGraph {
+            adjacency,
+            lengths,
+            index,
+        }
+    }
+
+    /// Systems connected to the given one, along with the connecting lane.
+    pub fn neighbours(&self, system: SystemIndex) -> &[(SystemIndex, LaneIndex)] {
+        let system: usize = system.into();
+        &self.adjacency[system]
+    }
+
+    /// Shortest paths along lanes from the given system to all systems
+    /// within the given travel distance.
+    pub fn paths(&self, from: SystemIndex, max_distance: f64) -> Paths {
+        self.search(from, max_distance, |_| true)
+    }
+
+    /// Lanes along the shortest path between the given systems, if connected
+    /// through lanes for which `usable` holds.
+    pub fn route<F>(&self, from: SystemIndex, to: SystemIndex, usable: F) -> Option<Vec<LaneIndex>>
+    where
+        F: Fn(LaneIndex) -> bool,
+    {
+        self.search(from, f64::INFINITY, usable).lanes(to)
+    }
+
+    /// Cheapest route in fuel for the ship from its departure system to the
+    /// given one, passing only through systems for which `usable` holds.
+    /// Besides following lanes the ship may jump directly to systems within
+    /// its jump range, and it may never run dry between systems where it can
+    /// refuel. Explains why if there is no such route.
+    pub fn plan<F>(
+        &self,
+        world: &World,
+        ship: &Ship,
+        to: SystemIndex,
+        usable: F,
+    ) -> Result<Vec<Leg>, Error>
+    where
+        F: Fn(SystemIndex) -> bool,
+    {
+        let name = &world.systems[to].name;
+        if !usable(to) {
+            return Err(format_err!("{} has not been charted", name));
+        }
+
+        let from = ship.departure(&world.lanes);
+        let search = |fuel, capacity| {
+            self.cheapest(world, from, to, ship.jump_range, fuel, capacity, &usable)
+        };
+        let reached = match search(ship.fuel, ship.fuel_capacity) {
+            Ok(route) => return Ok(route),
+            Err(_) => match search(f64::INFINITY, f64::INFINITY) {
+                Ok(_) => {
+                    return Err(format_err!(
+                        "Not enough fuel to reach {}, refuelling only at inhabited systems and gas giants",
+                        name
+                    ))
+                }
+                Err(reached) => reached,
+            },
+        };
+
+        // Even with unlimited fuel, the ship has to cross a gap wider than it can jump.
+        let target = &world.systems[to].location;
+        let distance = |system: SystemIndex| world.systems[system].location.distance(target);
+        let closest = reached
+            .into_iter()
+            .min_by(|&a, &b| {
+                distance(a)
+                    .partial_cmp(&distance(b))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(from);
+        Err(format_err!(
+            "{} lies {:.1} from {}, the closest reachable system, beyond the jump range of {:.1}",
+            name,
+            distance(closest),
+            world.systems[closest].name,
+            ship.jump_range
+        ))
+    }
+
+    /// Cheapest route in fuel between the given systems, starting with the
+    /// given fuel and filling up to the capacity when refuelling.
+    /// A route is cheaper than another to the same system only if it leaves
+    /// more fuel, so other routes are kept until then. Returns the systems
+    /// reached if the target was not.
+    #[allow(clippy::too_many_arguments)]
+    fn cheapest<F>(
+        &self,
+        world: &World,
+        from: SystemIndex,
+        to: SystemIndex,
+        jump_range: f64,
+        fuel: f64,
+        capacity: f64,
+        usable: F,
+    ) -> Result<Vec<Leg>, Vec<SystemIndex>>
+    where
+        F: Fn(SystemIndex) -> bool,
+    {
+        let refuel = |system: SystemIndex, fuel: f64| {
+            if world.systems[system].refuels(&world.bodies) {
+                fuel.max(capacity)
+            } else {
+                fuel
+            }
+        };
+
+        // Most fuel left on arrival at each system by any route settled so far.
+        let mut best = vec![f64::NEG_INFINITY; world.systems.len()];
+        let mut reached = vec![];
+        let mut labels = vec![Label {
+            system: from,
+            fuel: refuel(from, fuel),
+            previous: None,
+        }];
+        let mut queue = BinaryHeap::new();
+        queue.push(Candidate(0., 0));
+
+        while let Some(Candidate(cost, id)) = queue.pop() {
+            let (system, fuel) = (labels[id].system, labels[id].fuel);
+            let system_id: usize = system.into();
+            if fuel <= best[system_id] {
+                continue;
+            }
+            if best[system_id] == f64::NEG_INFINITY {
+                reached.push(system);
+            }
+            best[system_id] = fuel;
+
+            if system == to {
+                let mut route = vec![];
+                let mut current = id;
+                while let Some((previous, leg)) = labels[current].previous {
+                    route.push(leg);
+                    current = previous;
+                }
+                route.reverse();
+                return Ok(route);
+            }
+
+            let location = &world.systems[system].location;
+            let lanes = self.neighbours(system).iter().map(|&(other, lane)| {
+                let lane_id: usize = lane.into();
+                (other, Leg::Lane(lane), self.lengths[lane_id])
+            });
+            let jumps = self
+                .index
+                .within(location, jump_range)
+                .into_iter()
+                .map(SystemIndex::from)
+                .filter(|&other| {
+                    let joined = self
+                        .neighbours(system)
+                        .iter()
+                        .any(|&(next, _)| next == other);
+                    other != system && !joined
+                })
+                .map(|other| {
+                    let length = world.systems[other].location.distance(location);
+                    (other, Leg::Jump(other), length)
+                });
+
+            for (other, leg, length) in lanes.chain(jumps) {
+                let used = length * leg.fuel_per_distance();
+                if used > fuel || !usable(other) {
+                    continue;
+                }
+                let left = refuel(other, fuel - used);
+                let other_id: usize = other.into();
+                if left > best[other_id] {
+                    labels.push(Label {
+                        system: other,
+                        fuel: left,
+                        previous: Some((id, leg)),
+                    });
+                    queue.push(Candidate(cost + used, labels.len() - 1));
+                }
+            }
+        }
+        Err(reached)
+    }
+
+    /// Shortest paths within the given travel distance, only along usable lanes.
+    fn search<F>(&self, from: SystemIndex, max_distance: f64, usable: F) -> Paths
+    where
+        F: Fn(LaneIndex) -> bool,
+    {
+        let mut reached = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        reached.insert(from, (0., None));
+        queue.push(Candidate(0., from));
+
+        while let Some(Candidate(distance, system)) = queue.pop() {
+            if distance > reached[&system].0 {
+                continue;
+            }
+            for &(other, lane) in self.neighbours(system) {
+                let lane_id: usize = lane.into();
+                let total = distance + self.lengths[lane_id];
+                let shorter = reached.get(&other).is_none_or(|&(best, _)| total < best);
+                if total <= max_distance && shorter && usable(lane) {
+                    reached.insert(other, (total, Some((system, lane))));
+                    queue.push(Candidate(total, other));
+                }
+            }
+        }
+        Paths { reached }
+    }
+}
+
+/// Shortest paths from a single system, see `LaneGraph::paths`.
+pub struct Paths {
+    /// Distance to each reached system and the previous system and lane on the path there.
+    reached: HashMap<SystemIndex, (f64, Option<(SystemIndex, LaneIndex)>)>,
+}
+
+impl Paths {
+    /// Travel distance to the given system, if reachable.
+    pub fn distance(&self, to: SystemIndex) -> Option<f64> {
+        self.reached.get(&to).map(|&(distance, _)| distance)
+    }
+
+    /// All reachable systems and their distances.
+    pub fn systems(&self) -> impl Iterator<Item = (SystemIndex, f64)> + '_ {
+        self.reached
+            .iter()
+            .map(|(&system, &(distance, _))| (system, distance))
+    }
+
+    /// Lanes to travel to reach the given system, in order, if reachable.
+    pub fn lanes(&self, to: SystemIndex) -> Option<Vec<LaneIndex>> {
+        let mut lanes = vec![];
+        let mut current = to;
+        while let Some((previous, lane)) = self.reached.get(&current)?.1 {
+            lanes.push(lane);
+            current = previous;
+        }
+        lanes.reverse();
+        Some(lanes)
+    }
+}
+
+/// Queue entry ordered so that the closest entry is popped first.
+struct Candidate<T>(f64, T);
+
+impl<T> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Candidate<T> {}
+
+impl<T> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entity::astronomical::{Lane, System},
+        point::Point,
+    };
+
+    #[test]
+    fn test_paths() {
+        // A square with a long detour: 0 - 1 - 2 is shorter than 0 - 3 - 2.
+        let mut world = World::new();
+        for (x, y) in &[(0., 0.), (1., 0.), (2., 0.), (1., 5.)] {
+            world.systems.insert(
+                System::builder()
+                    .location(Point::new(*x, *y))
+                    .name("")
+                    .build(),
+            );
+        }
+        for (from, to) in &[(0, 1), (1, 2), (0, 3), (3, 2)] {
+            let (from, to) = (SystemIndex::from(*from), SystemIndex::from(*to));
+            let length = world.systems[from]
+                .location
+                .distance(&world.systems[to].location);
+            world.lanes.insert(Lane::new(from, to, length));
+        }
+
+        let graph = LaneGraph::new(&world);
+        let paths = graph.paths(SystemIndex::from(0), 100.);
+        assert_eq!(paths.distance(SystemIndex::from(2)), Some(2.));
+        assert_eq!(
+            paths.lanes(SystemIndex::from(2)),
+            Some(vec![LaneIndex::from(0), LaneIndex::from(1)])
+        );
+        assert_eq!(paths.lanes(SystemIndex::from(0)), Some(vec![]));
+
+        // Systems further away than the maximum distance are not reached.
+        let paths = graph.paths(SystemIndex::from(0), 1.5);
+        assert_eq!(paths.distance(SystemIndex::from(2)), None);
+        assert_eq!(paths.systems().count(), 2);
+        assert_eq!(
+            graph.route(SystemIndex::from(3), SystemIndex::from(1), |_| true),
+            Some(vec![LaneIndex::from(2), LaneIndex::from(0)])
+        );
+        assert_eq!(
+            graph.route(SystemIndex::from(3), SystemIndex::from(1), |lane| lane
+                != LaneIndex::from(2)),
+            Some(vec![LaneIndex::from(3), LaneIndex::from(1)])
+        );
+    }
+
+    #[test]
+    fn test_plan() {
+        // Lanes join the first three systems on a line, the last is only reachable by jumping.
+        let mut world = World::new();
+        for x in &[0., 10., 20., 40.] {
+            world.systems.insert(
+                System::builder()
+                    .location(Point::new(*x, 0.))
+                    .name("")
+                    .build(),
+            );
+        }
+        for from in 0..2 {
+            let (from, to) = (SystemIndex::from(from), SystemIndex::from(from + 1));
+            world.lanes.insert(Lane::new(from, to, 10.));
+        }
+        let graph = LaneGraph::new(&world);
+        let mut ship = Ship::builder()
+            .name("Test")
+            .location(Point::origin())
+            .system(SystemIndex::from(0))
+            .speed(1.)
+            .fuel(10.)
+            .fuel_capacity(10.)
+            .jump_range(25.)
+            .build();
+
+        let plan =
+            |ship: &Ship, world: &World| graph.plan(world, ship, SystemIndex::from(3), |_| true);
+        assert_eq!(
+            plan(&ship, &world).unwrap(),
+            vec![
+                Leg::Lane(LaneIndex::from(0)),
+                Leg::Lane(LaneIndex::from(1)),
+                Leg::Jump(SystemIndex::from(3))
+            ]
+        );
+
+        // Too little fuel for the jump, unless refuelling before it.
+        ship.fuel = 2.5;
+        ship.fuel_capacity = 2.5;
+        assert!(plan(&ship, &world)
+            .unwrap_err()
+            .to_string()
+            .contains("fuel"));
+        world.systems[SystemIndex::from(2)].population = 100;
+        assert_eq!(plan(&ship, &world).unwrap().len(), 3);
+
+        ship.jump_range = 15.;
+        assert!(plan(&ship, &world)
+            .unwrap_err()
+            .to_string()
+            .contains("jump range"));
+    }
+}
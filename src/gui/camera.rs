@@ -0,0 +1,107 @@
+use super::*;
+
+use crate::point::Point;
+use ggez::graphics::Point2;
+
+/// Maps between world coordinates and screen coordinates.
+#[derive(Clone)]
+pub struct Camera {
+    /// World location at the center of the screen.
+    pub center: Point,
+    /// Scale in pixels per world unit.
+    pub zoom: f64,
+    screen: RenderArea,
+}
+
+impl Camera {
+    /// Closest allowed zoom level.
+    const MAX_ZOOM: f64 = 100.;
+
+    /// Farthest allowed zoom level.
+    const MIN_ZOOM: f64 = 0.1;
+
+    /// Create a new camera centered at the origin, covering the given screen area.
+    pub fn new(screen: RenderArea) -> Self {
+        Camera {
+            center: Point::origin(),
+            zoom: 1.,
+            screen,
+        }
+    }
+
+    /// The screen area covered by the camera.
+    pub fn screen(&self) -> RenderArea {
+        self.screen
+    }
+
+    /// Change the screen area covered by the camera.
+    pub fn resize(&mut self, screen: RenderArea) {
+        self.screen = screen;
+    }
+
+    /// Convert the world location to a screen position.
+    pub fn to_screen(&self, point: &Point) -> Point2 {
+        Point2::new(
+            (f64::from(self.screen.x + self.screen.w / 2.) + (point.x - self.center.x) * self.zoom)
+                as f32,
+            (f64::from(self.screen.y + self.screen.h / 2.) + (point.y - self.center.y) * self.zoom)
+                as f32,
+        )
+    }
+
+    /// Convert the screen position to a world location.
+    pub fn to_world(&self, x: f32, y: f32) -> Point {
+        Point::new(
+            self.center.x + f64::from(x - self.screen.x - self.screen.w / 2.) / self.zoom,
+            self.center.y + f64::from(y - self.screen.y - self.screen.h / 2.) / self.zoom,
+        )
+    }
+
+    /// Returns the minimum and maximum world locations which are visible.
+    pub fn bounds(&self) -> (Point, Point) {
+        (
+            self.to_world(self.screen.left(), self.screen.top()),
+            self.to_world(self.screen.right(), self.screen.bottom()),
+        )
+    }
+
+    /// Move the camera the given distance in pixels.
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        self.center.x += f64::from(dx) / self.zoom;
+        self.center.y += f64::from(dy) / self.zoom;
+    }
+
+    /// Zoom by the given factor, keeping the world location at the screen position fixed.
+    pub fn zoom_at(&mut self, factor: f64, x: f32, y: f32) {
+        let anchor = self.to_world(x, y);
+        self.zoom = (self.zoom * factor).clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+        let moved = self.to_world(x, y);
+        self.center.x += anchor.x - moved.x;
+        self.center.y += anchor.y - moved.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_transform() {
+        let mut camera = Camera::new(RenderArea::new(0., 0., 800., 600.));
+        assert_eq!(camera.to_screen(&Point::origin()), Point2::new(400., 300.));
+        assert_eq!(camera.to_world(400., 300.), Point::origin());
+
+        camera.pan(100., 0.);
+        camera.zoom = 2.;
+        assert_eq!(
+            camera.to_screen(&Point::new(100., 0.)),
+            Point2::new(400., 300.)
+        );
+        assert_eq!(camera.bounds().0, Point::new(-100., -150.));
+
+        // Location under the cursor stays in place.
+        let before = camera.to_world(600., 100.);
+        camera.zoom_at(2., 600., 100.);
+        assert_eq!(camera.to_world(600., 100.), before);
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+use crate::point::Point;
+
+/// Overlay drawing a distance grid along with a scale bar.
+pub struct Grid {}
+
+impl Grid {
+    /// Minimum distance in pixels between grid lines.
+    const MIN_SPACING: f64 = 80.;
+
+    /// Create a new grid overlay.
+    pub fn new() -> Self {
+        Grid {}
+    }
+
+    /// Returns the smallest round distance, i.e 1, 2 or 5 times a power of ten,
+    /// which is at least the given distance.
+    fn spacing(distance: f64) -> f64 {
+        let magnitude = 10f64.powf(distance.log10().floor());
+        [1., 2., 5., 10.]
+            .iter()
+            .map(|factor| factor * magnitude)
+            .find(|spacing| *spacing >= distance)
+            .unwrap()
+    }
+}
+
+impl Component for Grid {
+    fn render(&self, area: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let (min, max) = camera.bounds();
+        let spacing = Self::spacing(Self::MIN_SPACING / camera.zoom);
+
+        let mut builder = MeshBuilder::new();
+        let mut x = (min.x / spacing).floor() * spacing;
+        while x <= max.x {
+            let top = camera.to_screen(&Point::new(x, min.y));
+            builder.line(&[top, Point2::new(top.x, area.bottom())], 1.);
+            x += spacing;
+        }
+        let mut y = (min.y / spacing).floor() * spacing;
+        while y <= max.y {
+            let left = camera.to_screen(&Point::new(min.x, y));
+            builder.line(&[left, Point2::new(area.right(), left.y)], 1.);
+            y += spacing;
+        }
+
+        let mesh = builder.build(ctx.draw_state())?;
+        graphics::set_color(ctx.draw_state(), Color::new(1., 1., 1., 0.1))?;
+        graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+
+        // Scale bar in the bottom right corner, one grid spacing long.
+        let length = (spacing * camera.zoom) as f32;
+        let right = area.right() - 20.;
+        let bottom = area.bottom() - 20.;
+        graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+        graphics::line(
+            ctx.draw_state(),
+            &[
+                Point2::new(right - length, bottom - 5.),
+                Point2::new(right - length, bottom),
+                Point2::new(right, bottom),
+                Point2::new(right, bottom - 5.),
+            ],
+            1.,
+        )?;
+        ctx.draw_text(
+            &format!("{} units", spacing),
+            Point2::new(right - length, bottom - 25.),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spacing() {
+        assert_eq!(Grid::spacing(0.8), 1.);
+        assert_eq!(Grid::spacing(1.5), 2.);
+        assert_eq!(Grid::spacing(3.), 5.);
+        assert_eq!(Grid::spacing(80.), 100.);
+        assert_eq!(Grid::spacing(200.), 200.);
+    }
+}
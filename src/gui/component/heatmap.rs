@@ -0,0 +1,84 @@
+use super::*;
+
+use crate::{entity::astronomical::SystemIndex, knowledge::Knowledge, spatial::SpatialIndex};
+use std::{collections::HashMap, rc::Rc};
+
+/// Overlay shading the map by the number of known systems in each screen cell.
+pub struct Heatmap {
+    index: Rc<SpatialIndex>,
+}
+
+impl Heatmap {
+    /// Size in pixels of each shaded cell.
+    const CELL: f32 = 16.;
+
+    /// Colors used from the least to the most dense cells.
+    const COLORS: [(f32, f32, f32); 5] = [
+        (0.1, 0.1, 0.6),
+        (0.1, 0.5, 0.8),
+        (0.2, 0.8, 0.3),
+        (0.9, 0.8, 0.1),
+        (0.9, 0.2, 0.1),
+    ];
+
+    /// Create a new heatmap using the given index of system locations.
+    pub fn new(index: Rc<SpatialIndex>) -> Self {
+        Heatmap { index }
+    }
+}
+
+impl Component for Heatmap {
+    fn render(&self, _: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+        let (min, max) = camera.bounds();
+
+        let mut cells: HashMap<(i32, i32), u32> = HashMap::new();
+        for id in self
+            .index
+            .query(min, max)
+            .into_iter()
+            .map(SystemIndex::from)
+        {
+            if world.knowledge(id) == Knowledge::Unknown {
+                continue;
+            }
+            let pos = camera.to_screen(&world.systems[id].location);
+            let cell = (
+                (pos.x / Self::CELL).floor() as i32,
+                (pos.y / Self::CELL).floor() as i32,
+            );
+            *cells.entry(cell).or_insert(0) += 1;
+        }
+
+        // Bucket cells by density so that each color is drawn as a single mesh.
+        let max_count = cells.values().cloned().max().unwrap_or(1) as f32;
+        let mut levels: Vec<MeshBuilder> =
+            Self::COLORS.iter().map(|_| MeshBuilder::new()).collect();
+        let mut used = vec![false; Self::COLORS.len()];
+        for ((x, y), count) in cells {
+            let density = (count as f32 / max_count).sqrt();
+            let level =
+                ((density * Self::COLORS.len() as f32) as usize).min(Self::COLORS.len() - 1);
+            let (x, y) = (x as f32 * Self::CELL, y as f32 * Self::CELL);
+            levels[level].polygon(
+                DrawMode::Fill,
+                &[
+                    Point2::new(x, y),
+                    Point2::new(x + Self::CELL, y),
+                    Point2::new(x + Self::CELL, y + Self::CELL),
+                    Point2::new(x, y + Self::CELL),
+                ],
+            );
+            used[level] = true;
+        }
+
+        for (level, builder) in levels.iter().enumerate().filter(|(level, _)| used[*level]) {
+            let (r, g, b) = Self::COLORS[level];
+            let mesh = builder.build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::new(r, g, b, 0.35))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+        Ok(())
+    }
+}
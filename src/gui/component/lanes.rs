@@ -0,0 +1,47 @@
+use super::*;
+
+use crate::{entity::astronomical::Lane, knowledge::Knowledge};
+
+/// Overlay drawing the hyperlanes between systems known by the player.
+pub struct Lanes {}
+
+impl Lanes {
+    /// Create a new lane overlay.
+    pub fn new() -> Self {
+        Lanes {}
+    }
+}
+
+impl Component for Lanes {
+    fn render(&self, _: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+        let (min, max) = camera.bounds();
+
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        let known = |lane: &&Lane| {
+            world.knowledge(lane.from) != Knowledge::Unknown
+                && world.knowledge(lane.to) != Knowledge::Unknown
+        };
+        for lane in world.lanes.iter().filter(known) {
+            let from = &world.systems[lane.from].location;
+            let to = &world.systems[lane.to].location;
+            let outside = from.x.max(to.x) < min.x
+                || from.x.min(to.x) > max.x
+                || from.y.max(to.y) < min.y
+                || from.y.min(to.y) > max.y;
+            if !outside {
+                builder.line(&[camera.to_screen(from), camera.to_screen(to)], 1.);
+                empty = false;
+            }
+        }
+
+        if !empty {
+            let mesh = builder.build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::new(0.3, 0.6, 1., 0.5))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+/// Named map overlay which can be toggled from the legend.
+pub struct Overlay {
+    name: &'static str,
+    enabled: bool,
+    component: Box<dyn Component>,
+}
+
+impl Overlay {
+    /// Create a new, disabled, overlay.
+    pub fn new(name: &'static str, component: Box<dyn Component>) -> Self {
+        Overlay {
+            name,
+            enabled: false,
+            component,
+        }
+    }
+}
+
+/// Panel listing the map overlays, allowing each of them to be toggled.
+pub struct Legend {
+    overlays: Vec<Overlay>,
+    pub visible: bool,
+}
+
+impl Legend {
+    /// Distance in pixels from the screen corner.
+    const MARGIN: f32 = 10.;
+
+    /// Height in pixels of each overlay entry.
+    const ROW_HEIGHT: f32 = 20.;
+
+    /// Width in pixels of the panel.
+    const WIDTH: f32 = 150.;
+
+    /// Create a new legend over the given overlays.
+    pub fn new(overlays: Vec<Overlay>) -> Self {
+        Legend {
+            overlays,
+            visible: true,
+        }
+    }
+
+    /// Area covered by the panel.
+    fn area(&self) -> RenderArea {
+        RenderArea::new(
+            Self::MARGIN,
+            Self::MARGIN,
+            Self::WIDTH,
+            Self::ROW_HEIGHT * self.overlays.len() as f32,
+        )
+    }
+
+    /// Toggles the overlay at the given screen position.
+    /// Returns whether the position was within the panel.
+    pub fn click(&mut self, x: i32, y: i32) -> bool {
+        let area = self.area();
+        if !self.visible || !area.contains(Point2::new(x as f32, y as f32)) {
+            return false;
+        }
+
+        let row = ((y as f32 - area.y) / Self::ROW_HEIGHT) as usize;
+        if let Some(overlay) = self.overlays.get_mut(row) {
+            overlay.enabled = !overlay.enabled;
+        }
+        true
+    }
+
+    /// Renders all enabled overlays.
+    pub fn render_overlays(
+        &self,
+        area: &mut RenderArea,
+        ctx: &mut RenderContext,
+    ) -> GameResult<()> {
+        for overlay in self.overlays.iter().filter(|overlay| overlay.enabled) {
+            overlay.component.render(area, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl Component for Legend {
+    fn render(&self, _: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let area = self.area();
+        graphics::set_color(ctx.draw_state(), Color::new(0., 0., 0., 0.6))?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Fill, area)?;
+
+        for (row, overlay) in self.overlays.iter().enumerate() {
+            let y = area.y + row as f32 * Self::ROW_HEIGHT;
+            let mode = if overlay.enabled {
+                DrawMode::Fill
+            } else {
+                DrawMode::Line(1.)
+            };
+            graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+            graphics::rectangle(
+                ctx.draw_state(),
+                mode,
+                RenderArea::new(area.x + 5., y + 5., 10., 10.),
+            )?;
+            ctx.draw_text(overlay.name, Point2::new(area.x + 22., y + 2.))?;
+        }
+        Ok(())
+    }
+}
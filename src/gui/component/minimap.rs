@@ -0,0 +1,255 @@
+use super::*;
+
+use crate::{
+    entity::astronomical::SystemIndex,
+    knowledge::{Knowledge, Observer},
+    point::Point,
+};
+use ggez::graphics::Mesh;
+use std::cell::RefCell;
+
+/// Density cell meshes, one per brightness level.
+type Meshes = Vec<(Color, Mesh)>;
+
+/// Overview of the known galaxy at small scale, showing the camera viewport.
+/// Clicking or dragging within the minimap moves the camera.
+pub struct Minimap {
+    min: Point,
+    extent: f64,
+    dragging: bool,
+    /// Cached meshes, along with the number of known systems they were built from.
+    meshes: RefCell<Option<(usize, Meshes)>>,
+}
+
+impl Minimap {
+    /// Size in pixels of the minimap.
+    const SIZE: f32 = 180.;
+
+    /// Distance in pixels from the screen corner.
+    const MARGIN: f32 = 10.;
+
+    /// Number of density cells along each side.
+    const RESOLUTION: usize = 60;
+
+    /// Brightness levels used for the density cells.
+    const LEVELS: usize = 4;
+
+    /// Create a new minimap covering all systems in the world.
+    pub fn new(world: &World) -> Self {
+        let mut min = Point::new(f64::MAX, f64::MAX);
+        let mut max = Point::new(f64::MIN, f64::MIN);
+        for system in &world.systems {
+            min = Point::new(min.x.min(system.location.x), min.y.min(system.location.y));
+            max = Point::new(max.x.max(system.location.x), max.y.max(system.location.y));
+        }
+        if world.systems.len() == 0 {
+            min = Point::origin();
+            max = Point::origin();
+        }
+
+        // Square area, so that the galaxy keeps its proportions.
+        let extent = (max.x - min.x).max(max.y - min.y).max(1.) * 1.05;
+        let center = Point::new((min.x + max.x) / 2., (min.y + max.y) / 2.);
+        let min = Point::new(center.x - extent / 2., center.y - extent / 2.);
+
+        Minimap {
+            min,
+            extent,
+            dragging: false,
+            meshes: RefCell::new(None),
+        }
+    }
+
+    /// Area covered by the minimap, in the bottom left corner of the screen.
+    fn area(screen: RenderArea) -> RenderArea {
+        RenderArea::new(
+            screen.x + Self::MARGIN,
+            screen.bottom() - Self::MARGIN - Self::SIZE,
+            Self::SIZE,
+            Self::SIZE,
+        )
+    }
+
+    /// Convert the world location to a position within the minimap area.
+    fn to_minimap(&self, area: RenderArea, point: &Point) -> Point2 {
+        Point2::new(
+            area.x + ((point.x - self.min.x) / self.extent) as f32 * area.w,
+            area.y + ((point.y - self.min.y) / self.extent) as f32 * area.h,
+        )
+    }
+
+    /// Convert the position within the minimap area to a world location.
+    fn to_world(&self, area: RenderArea, x: f32, y: f32) -> Point {
+        Point::new(
+            self.min.x + f64::from((x - area.x) / area.w) * self.extent,
+            self.min.y + f64::from((y - area.y) / area.h) * self.extent,
+        )
+    }
+
+    /// Moves the camera on clicks and drags within the minimap.
+    /// Returns whether the event was consumed by the minimap.
+    pub fn handle_event(&mut self, event: &Event, camera: &mut Camera) -> bool {
+        let area = Self::area(camera.screen());
+        match *event {
+            Event::MouseDown {
+                button: MouseButton::Left,
+                x,
+                y,
+                ..
+            } if area.contains(Point2::new(x as f32, y as f32)) => {
+                self.dragging = true;
+                camera.center = self.to_world(area, x as f32, y as f32);
+                true
+            }
+            Event::MouseMove { x, y, .. } if self.dragging => {
+                let x = (x as f32).clamp(area.left(), area.right());
+                let y = (y as f32).clamp(area.top(), area.bottom());
+                camera.center = self.to_world(area, x, y);
+                true
+            }
+            Event::MouseUp { .. } | Event::FocusLost if self.dragging => {
+                self.dragging = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Number of systems known by the player within each density cell.
+    fn cells(&self, world: &World) -> Vec<u32> {
+        let mut cells = vec![0; Self::RESOLUTION * Self::RESOLUTION];
+        for (id, system) in world.systems.iter().enumerate() {
+            if world.knowledge(SystemIndex::from(id)) == Knowledge::Unknown {
+                continue;
+            }
+            let x =
+                ((system.location.x - self.min.x) / self.extent * Self::RESOLUTION as f64) as usize;
+            let y =
+                ((system.location.y - self.min.y) / self.extent * Self::RESOLUTION as f64) as usize;
+            let x = x.min(Self::RESOLUTION - 1);
+            let y = y.min(Self::RESOLUTION - 1);
+            cells[y * Self::RESOLUTION + x] += 1;
+        }
+        cells
+    }
+
+    /// Build one mesh of density cells per brightness level, relative to the minimap origin.
+    fn build_meshes(&self, ctx: &mut Context, world: &World) -> GameResult<Meshes> {
+        let cells = self.cells(world);
+        let max = cells.iter().cloned().max().unwrap_or(0).max(1) as f32;
+        let size = Self::SIZE / Self::RESOLUTION as f32;
+        let mut levels: Vec<_> = (0..Self::LEVELS)
+            .map(|_| (false, MeshBuilder::new()))
+            .collect();
+        for (i, count) in cells.iter().enumerate().filter(|(_, c)| **c > 0) {
+            let level = (((*count as f32 / max).sqrt() * Self::LEVELS as f32) as usize)
+                .min(Self::LEVELS - 1);
+            let x = (i % Self::RESOLUTION) as f32 * size;
+            let y = (i / Self::RESOLUTION) as f32 * size;
+            levels[level].0 = true;
+            levels[level].1.polygon(
+                DrawMode::Fill,
+                &[
+                    Point2::new(x, y),
+                    Point2::new(x + size, y),
+                    Point2::new(x + size, y + size),
+                    Point2::new(x, y + size),
+                ],
+            );
+        }
+
+        let mut meshes = vec![];
+        for (level, (used, builder)) in levels.iter().enumerate() {
+            if *used {
+                let brightness = (level + 1) as f32 / Self::LEVELS as f32;
+                meshes.push((Color::new(1., 1., 1., brightness), builder.build(ctx)?));
+            }
+        }
+        Ok(meshes)
+    }
+}
+
+impl Component for Minimap {
+    fn render(&self, area: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+        let minimap = Self::area(*area);
+
+        graphics::set_color(ctx.draw_state(), Color::new(0., 0., 0., 0.8))?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Fill, minimap)?;
+
+        // Rebuild whenever more systems become known.
+        let known = world
+            .chart(Observer::Player)
+            .map_or(world.systems.len(), |chart| {
+                chart.count(Knowledge::Detected)
+            });
+        let mut meshes = self.meshes.borrow_mut();
+        if meshes.as_ref().is_none_or(|(built, _)| *built != known) {
+            *meshes = Some((known, self.build_meshes(ctx.draw_state(), world)?));
+        }
+        for (color, mesh) in &meshes.as_ref().unwrap().1 {
+            graphics::set_color(ctx.draw_state(), *color)?;
+            graphics::draw(ctx.draw_state(), mesh, minimap.point(), 0.)?;
+        }
+
+        // Camera viewport, clipped to the minimap.
+        let (min, max) = camera.bounds();
+        let top_left = self.to_minimap(minimap, &min);
+        let bottom_right = self.to_minimap(minimap, &max);
+        let left = top_left.x.max(minimap.left());
+        let top = top_left.y.max(minimap.top());
+        let right = bottom_right.x.min(minimap.right());
+        let bottom = bottom_right.y.min(minimap.bottom());
+        graphics::set_color(ctx.draw_state(), Color::new(1., 0.8, 0.2, 1.))?;
+        if left < right && top < bottom {
+            graphics::rectangle(
+                ctx.draw_state(),
+                DrawMode::Line(1.),
+                RenderArea::new(left, top, right - left, bottom - top),
+            )?;
+        }
+
+        graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Line(1.), minimap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::astronomical::System;
+
+    #[test]
+    fn test_minimap_transform() {
+        let mut world = World::new();
+        for location in &[Point::new(-100., -50.), Point::new(100., 50.)] {
+            world
+                .systems
+                .insert(System::builder().location(*location).name("").build());
+        }
+        let minimap = Minimap::new(&world);
+        let area = RenderArea::new(10., 20., 100., 100.);
+
+        // Galaxy centered within the minimap.
+        let center = minimap.to_minimap(area, &Point::origin());
+        assert!((center.x - 60.).abs() < 1e-3 && (center.y - 70.).abs() < 1e-3);
+
+        let world_point = minimap.to_world(area, 35., 40.);
+        let back = minimap.to_minimap(area, &world_point);
+        assert!((back.x - 35.).abs() < 1e-3 && (back.y - 40.).abs() < 1e-3);
+
+        // Clicking within the minimap moves the camera there.
+        let mut camera = Camera::new(RenderArea::new(0., 0., 800., 600.));
+        let mut minimap = minimap;
+        let click = Event::MouseDown {
+            button: MouseButton::Left,
+            x: 100,
+            y: 500,
+            keymod: NOMOD,
+        };
+        assert!(minimap.handle_event(&click, &mut camera));
+        let expected = minimap.to_world(Minimap::area(camera.screen()), 100., 500.);
+        assert_eq!(camera.center, expected);
+    }
+}
@@ -1,6 +1,26 @@
 use super::*;
 
+use self::camera::Camera;
+use ggez::graphics::{self, Color, DrawMode, MeshBuilder, Point2};
+
+mod grid;
+mod heatmap;
+mod lanes;
+mod legend;
+mod minimap;
+mod ships;
+mod territories;
+mod trade;
+pub use self::grid::Grid;
+pub use self::heatmap::Heatmap;
+pub use self::lanes::Lanes;
+pub use self::legend::{Legend, Overlay};
+pub use self::minimap::Minimap;
+pub use self::ships::Ships;
+pub use self::territories::Territories;
+pub use self::trade::Trade;
+
 /// A renderable component.
 pub trait Component {
-    fn render(&self, area: &mut RenderArea, ctx: &mut RenderContext);
+    fn render(&self, area: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()>;
 }
@@ -0,0 +1,182 @@
+use super::*;
+
+use crate::{
+    entity::{faction::FactionIndex, ship::Role},
+    knowledge::Knowledge,
+    missions::{Objective, Status},
+    point::Point,
+};
+use std::collections::BTreeMap;
+
+/// Draws ships on the map, along with the remaining route of the player's ship
+/// and any convoys it is escorting.
+/// Ships are drawn where they are along their routes each frame, so they
+/// move smoothly between the daily simulation steps.
+pub struct Ships {}
+
+impl Ships {
+    /// Size in pixels of a ship marker.
+    const SIZE: f32 = 5.;
+
+    /// Size in pixels of the marker of a faction ship.
+    const FLEET_SIZE: f32 = 3.;
+
+    /// Create a new ship component.
+    pub fn new() -> Self {
+        Ships {}
+    }
+
+    /// Add the marker of a faction ship, shaped by its role.
+    fn add_marker(builder: &mut MeshBuilder, center: Point2, role: Role) {
+        let size = Self::FLEET_SIZE;
+        let point = |dx: f32, dy: f32| Point2::new(center.x + dx * size, center.y + dy * size);
+        match role {
+            Role::Trader => {
+                builder.circle(DrawMode::Fill, center, size, 0.5);
+            }
+            Role::Patrol => {
+                builder.polygon(
+                    DrawMode::Fill,
+                    &[
+                        point(-1., -1.),
+                        point(1., -1.),
+                        point(1., 1.),
+                        point(-1., 1.),
+                    ],
+                );
+            }
+            Role::Pirate => {
+                builder.polygon(
+                    DrawMode::Line(1.5),
+                    &[
+                        point(0., -1.5),
+                        point(1.5, 0.),
+                        point(0., 1.5),
+                        point(-1.5, 0.),
+                    ],
+                );
+            }
+        }
+    }
+
+    /// Draw the ships of the factions in known space within view, in the
+    /// color of their faction.
+    fn render_fleets(ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+        let (min, max) = camera.bounds();
+        let mut fleets: BTreeMap<usize, MeshBuilder> = BTreeMap::new();
+        for ship in world.ships.iter() {
+            let (faction, role) = match (ship.faction, ship.role) {
+                (Some(faction), Some(role)) => (faction, role),
+                _ => continue,
+            };
+            let location = &ship.location;
+            let visible = location.x >= min.x
+                && location.x <= max.x
+                && location.y >= min.y
+                && location.y <= max.y;
+            if visible && world.knowledge(ship.system) != Knowledge::Unknown {
+                let builder = fleets
+                    .entry(faction.into())
+                    .or_insert_with(MeshBuilder::new);
+                Self::add_marker(builder, camera.to_screen(location), role);
+            }
+        }
+
+        for (faction, builder) in fleets {
+            let (r, g, b) = world.factions[FactionIndex::from(faction)].color;
+            let mesh = builder.build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::from_rgb(r, g, b))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+        Ok(())
+    }
+
+    /// Draw the convoys the player is escorting, in the color of the player's ship.
+    fn render_convoys(ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+        let convoys: Vec<Point2> = world
+            .missions
+            .iter()
+            .filter(|mission| mission.status == Status::Active)
+            .filter_map(|mission| match mission.objective {
+                Objective::Escort { convoy, .. } => convoy,
+                _ => None,
+            })
+            .filter_map(|convoy| world.ships.get(convoy))
+            .map(|convoy| camera.to_screen(&convoy.location))
+            .collect();
+        if convoys.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder = MeshBuilder::new();
+        for center in convoys {
+            Self::add_marker(&mut builder, center, Role::Trader);
+        }
+        let mesh = builder.build(ctx.draw_state())?;
+        graphics::set_color(ctx.draw_state(), Color::new(0.3, 0.9, 1., 1.))?;
+        graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)
+    }
+}
+
+impl Component for Ships {
+    fn render(&self, _: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        Self::render_fleets(ctx)?;
+        Self::render_convoys(ctx)?;
+
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+        let player = match world.player.and_then(|id| world.ships.get(id)) {
+            Some(player) => player,
+            None => return Ok(()),
+        };
+
+        // Remaining route, starting at the current location.
+        if player.travelling() {
+            let mut points: Vec<Point2> = vec![camera.to_screen(&player.location)];
+            let mut system = player.system;
+            for &leg in &player.route {
+                system = leg.end(system, &world.lanes);
+                points.push(camera.to_screen(&world.systems[system].location));
+            }
+            let mesh = MeshBuilder::new()
+                .line(&points, 1.5)
+                .build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::new(0.3, 0.9, 1., 0.8))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+
+        let center = camera.to_screen(&player.location);
+        let marker = |dx: f32, dy: f32| Point2::new(center.x + dx, center.y + dy);
+        let mesh = MeshBuilder::new()
+            .polygon(
+                DrawMode::Fill,
+                &[
+                    marker(0., -Self::SIZE),
+                    marker(Self::SIZE, Self::SIZE),
+                    marker(-Self::SIZE, Self::SIZE),
+                ],
+            )
+            .build(ctx.draw_state())?;
+        graphics::set_color(ctx.draw_state(), Color::new(0.3, 0.9, 1., 1.))?;
+        graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+
+        // Ring around the destination.
+        if let Some(destination) = player.destination {
+            let location: &Point = &world.systems[destination].location;
+            let mesh = MeshBuilder::new()
+                .circle(
+                    DrawMode::Line(1.),
+                    camera.to_screen(location),
+                    Self::SIZE + 2.,
+                    0.5,
+                )
+                .build(ctx.draw_state())?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+        Ok(())
+    }
+}
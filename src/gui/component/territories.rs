@@ -0,0 +1,142 @@
+use super::*;
+
+use crate::{
+    entity::{astronomical::SystemIndex, faction::FactionIndex},
+    knowledge::Knowledge,
+    spatial::SpatialIndex,
+};
+use std::{collections::HashMap, rc::Rc};
+
+/// Overlay drawing the political map, i.e. the Voronoi cells around systems
+/// filled with the color of the owning faction, borders between factions,
+/// and markers around contested systems.
+pub struct Territories {
+    index: Rc<SpatialIndex>,
+}
+
+impl Territories {
+    /// Distance in pixels between sampled screen positions.
+    const STEP: f32 = 8.;
+
+    /// Maximum distance in world units for a location to be part of a system's cell.
+    const MAX_DISTANCE: f64 = 20.;
+
+    /// Create a new territory overlay using the given index of system locations.
+    pub fn new(index: Rc<SpatialIndex>) -> Self {
+        Territories { index }
+    }
+
+    /// Returns the faction owning the region at the given screen position,
+    /// if any and surveyed by the player.
+    fn owner(&self, world: &World, camera: &Camera, x: f32, y: f32) -> Option<FactionIndex> {
+        let location = camera.to_world(x, y);
+        self.index
+            .nearest(&location)
+            .map(SystemIndex::from)
+            .filter(|&id| world.knowledge(id) >= Knowledge::Surveyed)
+            .map(|id| &world.systems[id])
+            .filter(|system| system.location.distance(&location) <= Self::MAX_DISTANCE)
+            .and_then(|system| system.faction)
+    }
+}
+
+impl Component for Territories {
+    fn render(&self, area: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+
+        // Sample the owner at the center of each cell of a screen grid.
+        let columns = (area.w / Self::STEP).ceil() as usize;
+        let rows = (area.h / Self::STEP).ceil() as usize;
+        let mut owners = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                owners.push(self.owner(
+                    world,
+                    camera,
+                    area.x + (column as f32 + 0.5) * Self::STEP,
+                    area.y + (row as f32 + 0.5) * Self::STEP,
+                ));
+            }
+        }
+
+        // Fill owned cells, one mesh per faction.
+        let mut fills: HashMap<FactionIndex, MeshBuilder> = HashMap::new();
+        for row in 0..rows {
+            for column in 0..columns {
+                if let Some(owner) = owners[row * columns + column] {
+                    let x = area.x + column as f32 * Self::STEP;
+                    let y = area.y + row as f32 * Self::STEP;
+                    fills.entry(owner).or_insert_with(MeshBuilder::new).polygon(
+                        DrawMode::Fill,
+                        &[
+                            Point2::new(x, y),
+                            Point2::new(x + Self::STEP, y),
+                            Point2::new(x + Self::STEP, y + Self::STEP),
+                            Point2::new(x, y + Self::STEP),
+                        ],
+                    );
+                }
+            }
+        }
+        for (owner, builder) in fills {
+            let (r, g, b) = world.factions[owner].color;
+            let mesh = builder.build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::from_rgba(r, g, b, 60))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+
+        // Draw edges between neighbouring cells with different owners.
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        for row in 0..rows {
+            for column in 0..columns {
+                let owner = owners[row * columns + column];
+                let x = area.x + (column + 1) as f32 * Self::STEP;
+                let y = area.y + (row + 1) as f32 * Self::STEP;
+                if column + 1 < columns && owners[row * columns + column + 1] != owner {
+                    builder.line(&[Point2::new(x, y - Self::STEP), Point2::new(x, y)], 1.);
+                    empty = false;
+                }
+                if row + 1 < rows && owners[(row + 1) * columns + column] != owner {
+                    builder.line(&[Point2::new(x - Self::STEP, y), Point2::new(x, y)], 1.);
+                    empty = false;
+                }
+            }
+        }
+
+        if !empty {
+            let mesh = builder.build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::new(0.9, 0.9, 0.9, 0.6))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+
+        // Mark contested systems.
+        let (min, max) = camera.bounds();
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        for id in self
+            .index
+            .query(min, max)
+            .into_iter()
+            .map(SystemIndex::from)
+        {
+            let system = &world.systems[id];
+            if system.contested && world.knowledge(id) >= Knowledge::Surveyed {
+                builder.circle(
+                    DrawMode::Line(1.),
+                    camera.to_screen(&system.location),
+                    5.,
+                    0.5,
+                );
+                empty = false;
+            }
+        }
+        if !empty {
+            let mesh = builder.build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::new(1., 0.2, 0.2, 0.9))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+use crate::knowledge::Knowledge;
+
+/// Overlay drawing the trade volume along each lane, busier lanes thicker.
+pub struct Trade {}
+
+impl Trade {
+    /// Line width of the busiest lane.
+    const MAX_WIDTH: f32 = 5.;
+
+    /// Create a new trade overlay.
+    pub fn new() -> Self {
+        Trade {}
+    }
+}
+
+impl Component for Trade {
+    fn render(&self, _: &mut RenderArea, ctx: &mut RenderContext) -> GameResult<()> {
+        let camera = ctx.camera();
+        let world = ctx.game_state();
+        let (min, max) = camera.bounds();
+
+        let busiest = world
+            .lanes
+            .iter()
+            .map(|lane| lane.volume)
+            .fold(0., f64::max);
+        if busiest <= 0. {
+            return Ok(());
+        }
+
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        // Traffic is only seen on lanes where the player has surveyed both ends.
+        let surveyed = |system| world.knowledge(system) >= Knowledge::Surveyed;
+        for lane in world
+            .lanes
+            .iter()
+            .filter(|lane| lane.volume > 0. && surveyed(lane.from) && surveyed(lane.to))
+        {
+            let from = &world.systems[lane.from].location;
+            let to = &world.systems[lane.to].location;
+            let outside = from.x.max(to.x) < min.x
+                || from.x.min(to.x) > max.x
+                || from.y.max(to.y) < min.y
+                || from.y.min(to.y) > max.y;
+            if !outside {
+                // Square root so that quieter routes remain visible.
+                let width = 1. + (Trade::MAX_WIDTH - 1.) * (lane.volume / busiest).sqrt() as f32;
+                builder.line(&[camera.to_screen(from), camera.to_screen(to)], width);
+                empty = false;
+            }
+        }
+
+        if !empty {
+            let mesh = builder.build(ctx.draw_state())?;
+            graphics::set_color(ctx.draw_state(), Color::new(1., 0.7, 0.2, 0.7))?;
+            graphics::draw(ctx.draw_state(), &mesh, Point2::origin(), 0.)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use super::*;
+
+use self::camera::Camera;
+use crate::{config::Controls, save::Slots};
+use std::collections::HashMap;
+
+/// Named actions which views can respond to, independent of the raw input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    ZoomIn,
+    ZoomOut,
+    Select,
+    Back,
+    ToggleOverlay,
+    Search,
+    History,
+    Details,
+    Survey,
+    Claim,
+    Sell,
+    Build,
+    Deliver,
+    Outfit,
+    Save,
+    Load,
+    Pause,
+    NormalSpeed,
+    DoubleSpeed,
+    FastSpeed,
+    MaxSpeed,
+}
+
+/// Context used for handling events.
+pub struct EventContext<'a> {
+    action: Option<Action>,
+    game_state: &'a mut World,
+    camera: &'a mut Camera,
+    slots: &'a Slots,
+}
+
+impl<'a> EventContext<'a> {
+    /// Creates a new context.
+    pub fn new(
+        action: Option<Action>,
+        game_state: &'a mut World,
+        camera: &'a mut Camera,
+        slots: &'a Slots,
+    ) -> Self {
+        EventContext {
+            action,
+            game_state,
+            camera,
+            slots,
+        }
+    }
+
+    /// Retrieves the action bound to the event, if any.
+    pub fn action(&self) -> Option<Action> {
+        self.action
+    }
+
+    /// Retrieves the game state.
+    pub fn game_state(&self) -> &World {
+        self.game_state
+    }
+
+    /// Retrieves the game state for modification.
+    pub fn game_state_mut(&mut self) -> &mut World {
+        self.game_state
+    }
+
+    /// Retrieves the map camera.
+    pub fn camera(&mut self) -> &mut Camera {
+        self.camera
+    }
+
+    /// Retrieves the save slots.
+    pub fn slots(&self) -> &'a Slots {
+        self.slots
+    }
+}
+
+/// Physical input which can be bound to an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Input {
+    Key(Keycode),
+    Mouse(MouseButton),
+    WheelUp,
+    WheelDown,
+}
+
+/// Input together with the modifiers which has to be held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Chord {
+    input: Input,
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+}
+
+impl Chord {
+    /// Create a new chord from the input and the currently held modifiers.
+    fn new(input: Input, keymod: Mod) -> Self {
+        Chord {
+            input,
+            ctrl: keymod.intersects(LCTRLMOD | RCTRLMOD),
+            shift: keymod.intersects(LSHIFTMOD | RSHIFTMOD),
+            alt: keymod.intersects(LALTMOD | RALTMOD),
+        }
+    }
+
+    /// Parse a chord such as `Ctrl+S`, `Mouse Left` or `Wheel Up`.
+    /// Key names are the ones used by SDL.
+    fn parse(binding: &str) -> Option<Chord> {
+        let mut keymod = NOMOD;
+        let mut rest = binding.trim();
+        loop {
+            if rest.starts_with("Ctrl+") {
+                keymod.insert(LCTRLMOD);
+            } else if rest.starts_with("Shift+") {
+                keymod.insert(LSHIFTMOD);
+            } else if rest.starts_with("Alt+") {
+                keymod.insert(LALTMOD);
+            } else {
+                break;
+            }
+            rest = &rest[rest.find('+').unwrap() + 1..];
+        }
+
+        let input = match rest {
+            "Mouse Left" => Input::Mouse(MouseButton::Left),
+            "Mouse Middle" => Input::Mouse(MouseButton::Middle),
+            "Mouse Right" => Input::Mouse(MouseButton::Right),
+            "Wheel Up" => Input::WheelUp,
+            "Wheel Down" => Input::WheelDown,
+            name => Input::Key(Keycode::from_name(name)?),
+        };
+        Some(Chord::new(input, keymod))
+    }
+}
+
+/// Mapping from raw input events to actions.
+pub struct Bindings {
+    chords: HashMap<Chord, Action>,
+}
+
+impl Bindings {
+    /// Create bindings from the controls configuration.
+    /// Bindings which can not be parsed are skipped.
+    pub fn new(controls: &Controls) -> Self {
+        let mut bindings = Bindings {
+            chords: HashMap::new(),
+        };
+        bindings.bind(Action::PanLeft, &controls.pan_left);
+        bindings.bind(Action::PanRight, &controls.pan_right);
+        bindings.bind(Action::PanUp, &controls.pan_up);
+        bindings.bind(Action::PanDown, &controls.pan_down);
+        bindings.bind(Action::ZoomIn, &controls.zoom_in);
+        bindings.bind(Action::ZoomOut, &controls.zoom_out);
+        bindings.bind(Action::Select, &controls.select);
+        bindings.bind(Action::Back, &controls.back);
+        bindings.bind(Action::ToggleOverlay, &controls.toggle_overlay);
+        bindings.bind(Action::Search, &controls.search);
+        bindings.bind(Action::History, &controls.history);
+        bindings.bind(Action::Details, &controls.details);
+        bindings.bind(Action::Survey, &controls.survey);
+        bindings.bind(Action::Claim, &controls.claim);
+        bindings.bind(Action::Sell, &controls.sell);
+        bindings.bind(Action::Build, &controls.build);
+        bindings.bind(Action::Deliver, &controls.deliver);
+        bindings.bind(Action::Outfit, &controls.outfit);
+        bindings.bind(Action::Save, &controls.save);
+        bindings.bind(Action::Load, &controls.load);
+        bindings.bind(Action::Pause, &controls.pause);
+        bindings.bind(Action::NormalSpeed, &controls.normal_speed);
+        bindings.bind(Action::DoubleSpeed, &controls.double_speed);
+        bindings.bind(Action::FastSpeed, &controls.fast_speed);
+        bindings.bind(Action::MaxSpeed, &controls.max_speed);
+        bindings
+    }
+
+    /// Bind all the given inputs to the action.
+    fn bind(&mut self, action: Action, inputs: &[String]) {
+        for input in inputs {
+            match Chord::parse(input) {
+                Some(chord) => {
+                    if let Some(previous) = self.chords.insert(chord, action) {
+                        warn!(
+                            "Binding {} for {:?} overrides {:?}",
+                            input, action, previous
+                        );
+                    }
+                }
+                None => warn!("Ignoring unknown binding {} for {:?}", input, action),
+            }
+        }
+    }
+
+    /// Returns the action bound to the given event, if any.
+    pub fn action(&self, event: &Event) -> Option<Action> {
+        let chord = match *event {
+            Event::KeyDown { code, keymod, .. } => Chord::new(Input::Key(code), keymod),
+            Event::MouseDown { button, keymod, .. } => Chord::new(Input::Mouse(button), keymod),
+            Event::MouseWheel { y, .. } if y > 0 => Chord::new(Input::WheelUp, NOMOD),
+            Event::MouseWheel { y, .. } if y < 0 => Chord::new(Input::WheelDown, NOMOD),
+            _ => return None,
+        };
+        self.chords.get(&chord).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: Keycode, keymod: Mod) -> Event {
+        Event::KeyDown {
+            code,
+            keymod,
+            repeat: false,
+        }
+    }
+
+    #[test]
+    fn test_bindings() {
+        let controls = Controls {
+            back: vec![String::from("Escape"), String::from("Ctrl+S")],
+            ..Default::default()
+        };
+        let bindings = Bindings::new(&controls);
+
+        assert_eq!(
+            bindings.action(&key(Keycode::Left, NOMOD)),
+            Some(Action::PanLeft)
+        );
+        assert_eq!(
+            bindings.action(&key(Keycode::Escape, NOMOD)),
+            Some(Action::Back)
+        );
+        assert_eq!(
+            bindings.action(&key(Keycode::S, RCTRLMOD)),
+            Some(Action::Back)
+        );
+        assert_eq!(bindings.action(&key(Keycode::S, LSHIFTMOD)), None);
+        assert_eq!(
+            bindings.action(&Event::MouseWheel { x: 0, y: 1 }),
+            Some(Action::ZoomIn)
+        );
+        // Typing '+' is bound along with '=' on the same key.
+        assert_eq!(
+            bindings.action(&key(Keycode::Equals, LSHIFTMOD)),
+            Some(Action::ZoomIn)
+        );
+        assert_eq!(bindings.action(&Event::FocusLost), None);
+    }
+
+    #[test]
+    fn test_unknown_binding() {
+        assert!(Chord::parse("Ctrl+NotAKey").is_none());
+        assert!(Chord::parse("Mouse Left").is_some());
+    }
+}
@@ -1,25 +1,57 @@
 use ggez::{event::*, *};
 pub use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    time::{Duration, Instant},
+};
 
-use crate::world::World;
+use crate::{
+    calendar::{Clock, Speed},
+    config::Config,
+    gen::{self, Simulation},
+    save::{Base, Slots},
+    world::World,
+};
+mod camera;
 mod component;
+mod input;
+mod record;
 mod render;
 mod view;
 
+use self::camera::Camera;
+use self::input::{Action, Bindings, EventContext};
+use self::record::{Recorder, Replayer};
 use self::render::{RenderArea, RenderContext};
-use self::view::StateMachine;
+use self::view::{Galaxy, StateMachine};
 
 /// UI user interaction events.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum Event {
     MouseDown {
+        #[serde(with = "record::button")]
         button: MouseButton,
         x: i32,
         y: i32,
+        #[serde(with = "record::keymod")]
+        keymod: Mod,
     },
     MouseUp {
+        #[serde(with = "record::button")]
         button: MouseButton,
         x: i32,
         y: i32,
+        #[serde(with = "record::keymod")]
+        keymod: Mod,
+    },
+    MouseDoubleClick {
+        #[serde(with = "record::button")]
+        button: MouseButton,
+        x: i32,
+        y: i32,
+        #[serde(with = "record::keymod")]
+        keymod: Mod,
     },
     MouseMove {
         x: i32,
@@ -32,30 +64,178 @@ pub enum Event {
         y: i32,
     },
     KeyDown {
+        #[serde(with = "record::keycode")]
         code: Keycode,
+        #[serde(with = "record::keymod")]
+        keymod: Mod,
         repeat: bool,
     },
     KeyUp {
+        #[serde(with = "record::keycode")]
         code: Keycode,
+        #[serde(with = "record::keymod")]
+        keymod: Mod,
         repeat: bool,
     },
+    TextInput {
+        text: String,
+    },
+    Resize {
+        width: u32,
+        height: u32,
+    },
+    FocusGained,
+    FocusLost,
+}
+
+/// Character typed by the key with shift held on a US keyboard layout.
+fn shifted(c: char) -> char {
+    match c {
+        '1' => '!',
+        '2' => '@',
+        '3' => '#',
+        '4' => '$',
+        '5' => '%',
+        '6' => '^',
+        '7' => '&',
+        '8' => '*',
+        '9' => '(',
+        '0' => ')',
+        '-' => '_',
+        '=' => '+',
+        '[' => '{',
+        ']' => '}',
+        '\\' => '|',
+        ';' => ':',
+        '\'' => '"',
+        ',' => '<',
+        '.' => '>',
+        '/' => '?',
+        '`' => '~',
+        c => c.to_ascii_uppercase(),
+    }
+}
+
+/// Returns the text typed by the given key press, if any.
+/// ggez does not forward SDL text input events, so printable ASCII is derived
+/// from the keycode instead, assuming a US keyboard layout for shifted keys.
+fn text_input(code: Keycode, keymod: Mod) -> Option<String> {
+    if keymod.intersects(LCTRLMOD | RCTRLMOD | LALTMOD | RALTMOD | LGUIMOD | RGUIMOD) {
+        return None;
+    }
+
+    let value = code as i32;
+    if !(0x20..=0x7e).contains(&value) {
+        return None;
+    }
+    let c = value as u8 as char;
+    let shift = keymod.intersects(LSHIFTMOD | RSHIFTMOD);
+    let c = if c.is_ascii_alphabetic() {
+        // Caps lock only affects letters.
+        if shift ^ keymod.contains(CAPSMOD) {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        }
+    } else if shift {
+        shifted(c)
+    } else {
+        c
+    };
+    Some(c.to_string())
+}
+
+/// Last mouse press, used for detecting double clicks.
+struct Click {
+    button: MouseButton,
+    x: i32,
+    y: i32,
+    time: Instant,
+}
+
+impl Click {
+    /// Whether a press of the button at the given position and time completes
+    /// a double click.
+    fn double(&self, button: MouseButton, x: i32, y: i32, time: Instant) -> bool {
+        self.button == button
+            && time.duration_since(self.time) <= GUI::DOUBLE_CLICK_TIME
+            && (self.x - x).abs() <= GUI::DOUBLE_CLICK_DISTANCE
+            && (self.y - y).abs() <= GUI::DOUBLE_CLICK_DISTANCE
+    }
 }
 
 /// UI handler for dispatching events and holding main state.
 pub struct GUI {
     game_state: World,
     states: StateMachine,
+    bindings: Bindings,
+    camera: Camera,
+    recorder: Option<Recorder<File>>,
     frames: u64,
+    /// Simulation advanced once per whole day passed.
+    simulation: Simulation,
+    clock: Clock,
+    slots: Slots,
+    /// In-game days between autosaves, 0 if disabled.
+    autosave_days: u64,
+    /// Whole days passed since the last autosave.
+    unsaved_days: u64,
+    keymod: Mod,
+    last_click: Option<Click>,
 }
 
 impl GUI {
-    /// Create a new UI.
-    pub fn new(game_state: World) -> Self {
-        GUI {
+    /// Maximum time between two presses to register as a double click.
+    const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+    /// Maximum distance in pixels between two presses of a double click.
+    const DOUBLE_CLICK_DISTANCE: i32 = 4;
+
+    /// Real time spent simulating each frame when running at max speed.
+    const MAX_SPEED_BUDGET: Duration = Duration::from_millis(12);
+
+    /// Width in pixels reserved for the date and speed.
+    const STATUS_WIDTH: f32 = 220.;
+
+    /// Create a new UI, playing the world generated from the given base.
+    pub fn new(game_state: World, base: Base, config: &Config) -> Self {
+        let recorder = config.gui.record_events.as_ref().and_then(|path| {
+            info!("Recording UI events to {}", path);
+            Recorder::create(path)
+                .map_err(|e| warn!("Failed to create event recording: {}", e))
+                .ok()
+        });
+
+        let bindings = Bindings::new(&config.controls);
+        let mode = conf::Conf::new().window_mode;
+        let screen = RenderArea::new(0., 0., mode.width as f32, mode.height as f32);
+        let states = StateMachine::new(Box::new(Galaxy::new(&game_state)));
+
+        let mut gui = GUI {
             frames: 0,
+            simulation: gen::live(config),
+            clock: Clock::new(),
+            slots: Slots::new(&config.saves, base),
+            autosave_days: config.saves.autosave_days,
+            unsaved_days: 0,
             game_state,
-            states: StateMachine::default(),
+            states,
+            bindings,
+            camera: Camera::new(screen),
+            recorder: None,
+            keymod: NOMOD,
+            last_click: None,
+        };
+
+        if let Some(ref path) = config.gui.replay_events {
+            info!("Replaying UI events from {}", path);
+            match Replayer::load(path) {
+                Ok(replayer) => replayer.replay(&mut gui),
+                Err(e) => warn!("Failed to load event recording: {}", e),
+            }
         }
+        gui.recorder = recorder;
+        gui
     }
 
     /// Start the UI, i.e the event loop and rendering.
@@ -64,15 +244,93 @@ impl GUI {
         let ctx = &mut Context::load_from_conf("prospero", "holmgr", c).unwrap();
         event::run(ctx, self).unwrap();
     }
+
+    /// Dispatch the event, along with its bound action, to the current view,
+    /// returning whether it switched views.
+    fn dispatch(&mut self, event: Event) -> bool {
+        if self.states.current().is_none() {
+            return false;
+        }
+
+        if let Some(ref mut recorder) = self.recorder {
+            if let Err(e) = recorder.record(&event) {
+                warn!("Failed to record event, stopping recording: {}", e);
+                self.recorder = None;
+            }
+        }
+
+        let action = self.bindings.action(&event);
+
+        // The game speed is controlled from all views, except while typing.
+        let typing = self.states.current().is_some_and(|view| view.text_input());
+        let speed = match action {
+            Some(Action::Pause) => Some(Speed::Paused),
+            Some(Action::NormalSpeed) => Some(Speed::Normal),
+            Some(Action::DoubleSpeed) => Some(Speed::Double),
+            Some(Action::FastSpeed) => Some(Speed::Fast),
+            Some(Action::MaxSpeed) => Some(Speed::Max),
+            _ => None,
+        };
+        match speed {
+            Some(Speed::Paused) if !typing => {
+                self.clock.toggle_pause();
+                return false;
+            }
+            Some(speed) if !typing => {
+                self.clock.set_speed(speed);
+                return false;
+            }
+            _ => {}
+        }
+
+        let mut ctx =
+            EventContext::new(action, &mut self.game_state, &mut self.camera, &self.slots);
+        self.states.handle_event(event, &mut ctx)
+    }
 }
 
 impl event::EventHandler for GUI {
     fn mouse_button_down_event(&mut self, _ctx: &mut Context, button: MouseButton, x: i32, y: i32) {
-        self.states.handle_event(Event::MouseDown { button, x, y });
+        let keymod = self.keymod;
+        self.dispatch(Event::MouseDown {
+            button,
+            x,
+            y,
+            keymod,
+        });
+
+        let now = Instant::now();
+        let double_click = self
+            .last_click
+            .as_ref()
+            .is_some_and(|click| click.double(button, x, y, now));
+
+        if double_click {
+            self.last_click = None;
+            self.dispatch(Event::MouseDoubleClick {
+                button,
+                x,
+                y,
+                keymod,
+            });
+        } else {
+            self.last_click = Some(Click {
+                button,
+                x,
+                y,
+                time: now,
+            });
+        }
     }
 
     fn mouse_button_up_event(&mut self, _ctx: &mut Context, button: MouseButton, x: i32, y: i32) {
-        self.states.handle_event(Event::MouseUp { button, x, y });
+        let keymod = self.keymod;
+        self.dispatch(Event::MouseUp {
+            button,
+            x,
+            y,
+            keymod,
+        });
     }
 
     fn mouse_motion_event(
@@ -84,43 +342,173 @@ impl event::EventHandler for GUI {
         xrel: i32,
         yrel: i32,
     ) {
-        self.states
-            .handle_event(Event::MouseMove { x, y, xrel, yrel });
+        self.dispatch(Event::MouseMove { x, y, xrel, yrel });
     }
 
     fn mouse_wheel_event(&mut self, _ctx: &mut Context, x: i32, y: i32) {
-        self.states.handle_event(Event::MouseWheel { x, y });
+        self.dispatch(Event::MouseWheel { x, y });
+    }
+
+    fn key_down_event(&mut self, _ctx: &mut Context, code: Keycode, keymod: Mod, repeat: bool) {
+        self.keymod = keymod;
+        let switched = self.dispatch(Event::KeyDown {
+            code,
+            keymod,
+            repeat,
+        });
+        // The key opening or closing a view does not type into the next one.
+        if switched {
+            return;
+        }
+        if let Some(text) = text_input(code, keymod) {
+            self.dispatch(Event::TextInput { text });
+        }
+    }
+
+    fn key_up_event(&mut self, _ctx: &mut Context, code: Keycode, keymod: Mod, repeat: bool) {
+        self.keymod = keymod;
+        self.dispatch(Event::KeyUp {
+            code,
+            keymod,
+            repeat,
+        });
     }
 
-    fn key_down_event(&mut self, _ctx: &mut Context, code: Keycode, _keymod: Mod, repeat: bool) {
-        self.states.handle_event(Event::KeyDown { code, repeat });
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) {
+        if gained {
+            self.dispatch(Event::FocusGained);
+        } else {
+            // Modifier releases are not reported while unfocused.
+            self.keymod = NOMOD;
+            self.dispatch(Event::FocusLost);
+        }
     }
 
-    fn key_up_event(&mut self, _ctx: &mut Context, code: Keycode, _keymod: Mod, repeat: bool) {
-        self.states.handle_event(Event::KeyUp { code, repeat });
+    fn resize_event(&mut self, ctx: &mut Context, width: u32, height: u32) {
+        // Keep a 1:1 mapping between screen coordinates and pixels.
+        let area = RenderArea::new(0., 0., width as f32, height as f32);
+        if let Err(e) = graphics::set_screen_coordinates(ctx, area) {
+            warn!("Failed to resize screen coordinates: {}", e);
+        }
+        self.camera.resize(area);
+        self.dispatch(Event::Resize { width, height });
     }
 
     /// Update the UI.
-    fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+        // Exit once all views have been closed.
+        if self.states.current().is_none() {
+            ctx.quit()?;
+        }
+
+        // Advance game time, simulating each whole day passed.
+        let start = Instant::now();
+        match self
+            .clock
+            .advance(timer::duration_to_f64(timer::get_delta(ctx)))
+        {
+            Some(days) => {
+                self.game_state.travel(days);
+                self.game_state.explore();
+                while self.clock.tick() {
+                    self.simulation.step(&mut self.game_state);
+                    self.unsaved_days += 1;
+                }
+            }
+            None => {
+                while start.elapsed() < Self::MAX_SPEED_BUDGET {
+                    self.game_state.travel(1.);
+                    self.game_state.explore();
+                    self.simulation.step(&mut self.game_state);
+                    self.unsaved_days += 1;
+                }
+            }
+        }
+        if self.autosave_days > 0 && self.unsaved_days >= self.autosave_days {
+            if let Err(e) = self.slots.autosave(&self.game_state) {
+                warn!("Failed to autosave: {}", e);
+            }
+            self.unsaved_days = 0;
+        }
         Ok(())
     }
 
     /// Draw the UI.
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let now = Instant::now();
         graphics::clear(ctx);
+
+        // Render the current view, along with any views visible beneath it.
+        for view in self.states.visible() {
+            let mut render_ctx = RenderContext::new(&self.game_state, &self.camera, ctx);
+            view.render(&mut render_ctx)?;
+        }
+
+        // Date and game speed in the top right corner, above all views.
+        let mut render_ctx = RenderContext::new(&self.game_state, &self.camera, ctx);
+        let status = format!("{} ({})", self.game_state.date(), self.clock.speed().name());
+        let corner = graphics::Point2::new(self.camera.screen().right() - Self::STATUS_WIDTH, 10.);
+        render_ctx.draw_text(&status, corner)?;
+
         graphics::present(ctx);
         if self.frames % 100 == 0 {
-            debug!("FPS: {:.1}", timer::get_fps(ctx));
+            let frame_time = now.elapsed();
+            debug!(
+                "FPS: {:.1}, frame time: {:.2} ms",
+                timer::get_fps(ctx),
+                frame_time.as_secs() as f64 * 1_000.
+                    + f64::from(frame_time.subsec_micros()) / 1_000.
+            );
         }
         self.frames += 1;
 
-        // Render the current view, if any.
-        if let Some(view) = self.states.current() {
-            let mut render_ctx = RenderContext::new(&self.game_state, ctx);
-            view.render(&mut render_ctx);
-        }
-
         timer::yield_now();
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_input() {
+        assert_eq!(text_input(Keycode::A, NOMOD), Some("a".to_string()));
+        assert_eq!(text_input(Keycode::A, LSHIFTMOD), Some("A".to_string()));
+        assert_eq!(text_input(Keycode::A, CAPSMOD), Some("A".to_string()));
+        assert_eq!(
+            text_input(Keycode::A, RSHIFTMOD | CAPSMOD),
+            Some("a".to_string())
+        );
+        assert_eq!(text_input(Keycode::Num1, NOMOD), Some("1".to_string()));
+        assert_eq!(text_input(Keycode::Num1, LSHIFTMOD), Some("!".to_string()));
+        assert_eq!(text_input(Keycode::Num1, CAPSMOD), Some("1".to_string()));
+        assert_eq!(text_input(Keycode::Slash, RSHIFTMOD), Some("?".to_string()));
+        assert_eq!(text_input(Keycode::Space, LSHIFTMOD), Some(" ".to_string()));
+
+        // Shortcuts and non-printable keys type nothing.
+        assert_eq!(text_input(Keycode::A, LCTRLMOD), None);
+        assert_eq!(text_input(Keycode::Return, NOMOD), None);
+        assert_eq!(text_input(Keycode::Left, NOMOD), None);
+    }
+
+    #[test]
+    fn test_double_click() {
+        let time = Instant::now();
+        let click = Click {
+            button: MouseButton::Left,
+            x: 100,
+            y: 100,
+            time,
+        };
+        assert!(click.double(
+            MouseButton::Left,
+            102,
+            97,
+            time + Duration::from_millis(200)
+        ));
+        assert!(!click.double(MouseButton::Right, 100, 100, time));
+        assert!(!click.double(MouseButton::Left, 110, 100, time));
+        assert!(!click.double(MouseButton::Left, 100, 100, time + Duration::from_secs(1)));
+    }
+}
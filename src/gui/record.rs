@@ -0,0 +1,235 @@
+use super::*;
+
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    path::Path,
+};
+
+/// Single recorded event, with the time in milliseconds since recording started.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Entry {
+    pub time: u64,
+    pub event: Event,
+}
+
+/// Records a stream of events, writing one JSON entry per line.
+pub struct Recorder<W: Write> {
+    writer: W,
+    start: Instant,
+}
+
+impl Recorder<File> {
+    /// Create a recorder writing to the file at the given path.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Ok(Recorder::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// Create a recorder writing to the given writer.
+    pub fn new(writer: W) -> Self {
+        Recorder {
+            writer,
+            start: Instant::now(),
+        }
+    }
+
+    /// Record the given event.
+    pub fn record(&mut self, event: &Event) -> Result<(), Error> {
+        let elapsed = self.start.elapsed();
+        let entry = Entry {
+            time: elapsed.as_secs() * 1_000 + u64::from(elapsed.subsec_millis()),
+            event: event.clone(),
+        };
+        serde_json::to_writer(&mut self.writer, &entry)?;
+        writeln!(self.writer)?;
+        Ok(())
+    }
+}
+
+/// Replays a recorded stream of events without a window.
+pub struct Replayer {
+    entries: Vec<Entry>,
+}
+
+impl Replayer {
+    /// Load a recording from the file at the given path.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Replayer::from_reader(File::open(path)?)
+    }
+
+    /// Load a recording from the given reader.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        let mut entries = vec![];
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(Replayer { entries })
+    }
+
+    /// The recorded entries, in order.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Feed all recorded events into the UI state machine, in order.
+    /// No window is needed since nothing is rendered.
+    /// Stops early if the state machine runs out of views.
+    pub fn replay(&self, gui: &mut GUI) {
+        for entry in &self.entries {
+            if gui.states.current().is_none() {
+                break;
+            }
+            gui.dispatch(entry.event.clone());
+        }
+    }
+}
+
+/// Serialization of keycodes as their SDL values.
+pub mod keycode {
+    use super::*;
+    use serde::{de, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(code: &Keycode, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(*code as i32)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Keycode, D::Error> {
+        let value = i32::deserialize(deserializer)?;
+        Keycode::from_i32(value)
+            .ok_or_else(|| de::Error::custom(format!("unknown keycode {}", value)))
+    }
+}
+
+/// Serialization of modifier state as its bit flags.
+pub mod keymod {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(keymod: &Mod, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(keymod.bits())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Mod, D::Error> {
+        Ok(Mod::from_bits_truncate(u16::deserialize(deserializer)?))
+    }
+}
+
+/// Serialization of mouse buttons as their SDL values.
+pub mod button {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        button: &MouseButton,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*button as u8)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<MouseButton, D::Error> {
+        Ok(match u8::deserialize(deserializer)? {
+            1 => MouseButton::Left,
+            2 => MouseButton::Middle,
+            3 => MouseButton::Right,
+            4 => MouseButton::X1,
+            5 => MouseButton::X2,
+            _ => MouseButton::Unknown,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use view::{StateMachine, Trans, View};
+
+    /// Pushes a new view on select and pops on back.
+    struct NavigationView {}
+    impl View for NavigationView {
+        fn handle_event(&mut self, _: Event, ctx: &mut EventContext) -> Trans {
+            match ctx.action() {
+                Some(Action::Select) => Trans::Push(Box::new(NavigationView {})),
+                Some(Action::Back) => Trans::Pop,
+                _ => Trans::None,
+            }
+        }
+
+        fn render(&self, _: &mut RenderContext) -> GameResult<()> {
+            Ok(())
+        }
+
+        // Keep all pushed views visible, so that the stack depth can be checked.
+        fn transparent(&self) -> bool {
+            true
+        }
+    }
+
+    /// Replay the recorded events on a new UI, returning the depth of its view stack.
+    fn depth_after(buffer: &[u8]) -> usize {
+        let replayer = Replayer::from_reader(buffer).unwrap();
+        let config = toml::from_str(include_str!("../../Config.toml")).unwrap();
+        let mut gui = GUI::new(World::new(), Base::default(), &config);
+        gui.states = StateMachine::new(Box::new(NavigationView {}));
+        replayer.replay(&mut gui);
+        gui.states.visible().len()
+    }
+
+    fn click(button: MouseButton, keymod: Mod) -> Event {
+        Event::MouseDown {
+            button,
+            x: 10,
+            y: 20,
+            keymod,
+        }
+    }
+
+    #[test]
+    fn test_record_replay() {
+        let events = vec![
+            click(MouseButton::Left, NOMOD),
+            Event::TextInput {
+                text: String::from("a"),
+            },
+            Event::Resize {
+                width: 800,
+                height: 600,
+            },
+            click(MouseButton::Right, NOMOD),
+            Event::KeyDown {
+                code: Keycode::Escape,
+                keymod: NOMOD,
+                repeat: false,
+            },
+        ];
+
+        let record = |events: &[Event]| {
+            let mut buffer = vec![];
+            {
+                let mut recorder = Recorder::new(&mut buffer);
+                for event in events {
+                    recorder.record(event).unwrap();
+                }
+            }
+            buffer
+        };
+
+        let buffer = record(&events);
+        let replayer = Replayer::from_reader(&buffer[..]).unwrap();
+        let replayed: Vec<_> = replayer.entries().iter().map(|e| e.event.clone()).collect();
+        assert_eq!(replayed, events);
+
+        // Select pushes a view, the right click is ignored by it and back pops
+        // the pushed view.
+        assert_eq!(depth_after(&record(&events[..4])), 2);
+        assert_eq!(depth_after(&buffer), 1);
+    }
+}
@@ -1,6 +1,7 @@
 use super::*;
 
-use ggez::graphics::Rect;
+use self::camera::Camera;
+use ggez::graphics::{self, Point2, Rect, Text};
 
 /// Area which the rendering should take place in.
 pub type RenderArea = Rect;
@@ -9,25 +10,39 @@ pub type DrawContext = Context;
 /// Context used for rendering.
 pub struct RenderContext<'a> {
     game_state: &'a World,
+    camera: &'a Camera,
     draw_state: &'a mut Context,
 }
 
 impl<'a> RenderContext<'a> {
     /// Creates a new context.
-    pub fn new(game_state: &'a World, draw_state: &'a mut DrawContext) -> Self {
+    pub fn new(game_state: &'a World, camera: &'a Camera, draw_state: &'a mut DrawContext) -> Self {
         RenderContext {
             game_state,
+            camera,
             draw_state,
         }
     }
 
     /// Retrives the game state.
-    pub fn game_state(&self) -> &World {
+    pub fn game_state(&self) -> &'a World {
         self.game_state
     }
 
+    /// Retrives the map camera.
+    pub fn camera(&self) -> &'a Camera {
+        self.camera
+    }
+
     /// Retrives the drawing context.
     pub fn draw_state(&mut self) -> &mut DrawContext {
         self.draw_state
     }
+
+    /// Draws the text using the default font, with its top left corner at the given position.
+    pub fn draw_text(&mut self, text: &str, dest: Point2) -> GameResult<()> {
+        let font = self.draw_state.default_font.clone();
+        let text = Text::new(self.draw_state, text, &font)?;
+        graphics::draw(self.draw_state, &text, dest, 0.)
+    }
 }
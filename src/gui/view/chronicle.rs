@@ -0,0 +1,134 @@
+use super::*;
+
+use crate::{entity::astronomical::SystemIndex, history::HistoryEvent, knowledge::Knowledge};
+use ggez::graphics::{self, Color, DrawMode, Point2};
+
+/// Overlay listing the history of the world, newest first, optionally
+/// filtered to the events of a single system.
+pub struct Chronicle {
+    system: Option<SystemIndex>,
+    filtered: bool,
+    scroll: usize,
+}
+
+impl Chronicle {
+    /// Height in pixels of each row.
+    const ROW_HEIGHT: f32 = 20.;
+
+    /// Width in pixels of the panel.
+    const WIDTH: f32 = 640.;
+
+    /// Margin in pixels around the panel.
+    const MARGIN: f32 = 40.;
+
+    /// Create a new history view, showing only events of the system if given.
+    pub fn new(system: Option<SystemIndex>) -> Self {
+        Chronicle {
+            system,
+            filtered: system.is_some(),
+            scroll: 0,
+        }
+    }
+
+    /// The events shown, newest first.
+    fn events<'a>(&self, world: &'a World) -> Vec<&'a HistoryEvent> {
+        world
+            .history
+            .iter()
+            .rev()
+            .filter(|event| !self.filtered || event.kind.system() == self.system)
+            .filter(|event| {
+                // Events in systems the player has not surveyed are unheard of.
+                event
+                    .kind
+                    .system()
+                    .is_none_or(|system| world.knowledge(system) >= Knowledge::Surveyed)
+            })
+            .collect()
+    }
+
+    /// Area covered by the panel.
+    fn area(screen: RenderArea) -> RenderArea {
+        let width = Self::WIDTH.min(screen.w - 2. * Self::MARGIN);
+        RenderArea::new(
+            screen.x + (screen.w - width) / 2.,
+            screen.y + Self::MARGIN,
+            width,
+            screen.h - 2. * Self::MARGIN,
+        )
+    }
+
+    /// Number of event rows fitting in the panel, below the title.
+    fn rows(area: RenderArea) -> usize {
+        ((area.h / Self::ROW_HEIGHT) as usize).saturating_sub(1)
+    }
+}
+
+impl View for Chronicle {
+    fn handle_event(&mut self, _: Event, ctx: &mut EventContext) -> Trans {
+        let count = self.events(ctx.game_state()).len();
+        let rows = Self::rows(Self::area(ctx.camera().screen()));
+        match ctx.action() {
+            Some(Action::Back) | Some(Action::History) => return Trans::Pop,
+            Some(Action::PanUp) | Some(Action::ZoomIn) => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            Some(Action::PanDown) | Some(Action::ZoomOut) => {
+                self.scroll = (self.scroll + 1).min(count.saturating_sub(rows));
+            }
+            Some(Action::ToggleOverlay) if self.system.is_some() => {
+                self.filtered = !self.filtered;
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        Trans::None
+    }
+
+    fn render(&self, ctx: &mut RenderContext) -> GameResult<()> {
+        let world = ctx.game_state();
+        let area = Self::area(ctx.camera().screen());
+
+        graphics::set_color(ctx.draw_state(), Color::new(0., 0., 0., 0.85))?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Fill, area)?;
+        graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Line(1.), area)?;
+
+        let title = match self.system {
+            Some(id) if self.filtered => {
+                format!("History of {} (Tab: all events)", world.systems[id].name)
+            }
+            Some(id) => format!(
+                "History of the galaxy (Tab: only {})",
+                world.systems[id].name
+            ),
+            None => "History of the galaxy".to_string(),
+        };
+        ctx.draw_text(&title, Point2::new(area.x + 5., area.y + 2.))?;
+
+        let events = self.events(world);
+        if events.is_empty() {
+            return ctx.draw_text(
+                "Nothing of note happened.",
+                Point2::new(area.x + 15., area.y + Self::ROW_HEIGHT + 2.),
+            );
+        }
+        for (row, event) in events
+            .iter()
+            .skip(self.scroll)
+            .take(Self::rows(area))
+            .enumerate()
+        {
+            let y = area.y + (row + 1) as f32 * Self::ROW_HEIGHT + 2.;
+            ctx.draw_text(
+                &format!("{}: {}", event.date(), event.kind.describe(world)),
+                Point2::new(area.x + 15., y),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn transparent(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,324 @@
+use super::*;
+
+use crate::{
+    economy::Commodity,
+    entity::astronomical::{BodyIndex, SystemIndex},
+    gen::Seed,
+    knowledge::{Knowledge, Observer},
+    missions::Status,
+};
+use ggez::graphics::{self, Color, DrawMode, Point2};
+
+/// Overlay showing the details of a single system, from which the player can
+/// survey its bodies, register or sell claims on them, exploit them and
+/// deliver their output, or take on missions from its board.
+pub struct Details {
+    system: SystemIndex,
+    /// Whether the mission board is shown instead of the bodies.
+    board: bool,
+    selected: usize,
+    message: String,
+}
+
+impl Details {
+    /// Height in pixels of each row.
+    const ROW_HEIGHT: f32 = 20.;
+
+    /// Width in pixels of the panel.
+    const WIDTH: f32 = 720.;
+
+    /// Margin in pixels around the panel.
+    const MARGIN: f32 = 40.;
+
+    /// Create a new detail view of the system.
+    pub fn new(system: SystemIndex) -> Self {
+        Details {
+            system,
+            board: false,
+            selected: 0,
+            message: String::new(),
+        }
+    }
+
+    /// Area covered by the panel.
+    fn area(screen: RenderArea) -> RenderArea {
+        let width = Self::WIDTH.min(screen.w - 2. * Self::MARGIN);
+        RenderArea::new(
+            screen.x + (screen.w - width) / 2.,
+            screen.y + Self::MARGIN,
+            width,
+            screen.h - 2. * Self::MARGIN,
+        )
+    }
+
+    /// The currently selected body, if the system has any.
+    fn body(&self, world: &World) -> Option<BodyIndex> {
+        world.systems[self.system]
+            .bodies
+            .get(self.selected)
+            .cloned()
+    }
+
+    /// Summary of what the player's survey found within the body.
+    fn survey(world: &World, body: BodyIndex) -> String {
+        let survey = match world.survey_of(body, Observer::Player) {
+            Some(survey) => survey,
+            None => return "not surveyed".to_string(),
+        };
+        let found = if survey.estimates.is_empty() {
+            "nothing found".to_string()
+        } else {
+            survey
+                .estimates
+                .iter()
+                .map(|estimate| format!("{} ~{:.0}", estimate.commodity.name(), estimate.amount))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let claim = match world.claim_on(body).map(|claim| claim.owner) {
+            Some(Observer::Player) => ", your claim".to_string(),
+            Some(Observer::Faction(faction)) => {
+                format!(", claimed by {}", world.factions[faction].name)
+            }
+            None => String::new(),
+        };
+        let operation = world.operation_on(body).map_or(String::new(), |operation| {
+            format!(
+                ", {} holding {:.0} units",
+                operation.kind.name(),
+                operation.stock.total()
+            )
+        });
+        format!(
+            "{} ({:.0}% accuracy{}{})",
+            found,
+            survey.accuracy * 100.,
+            claim,
+            operation
+        )
+    }
+
+    /// Missions offered on the board of the system.
+    fn offers(&self, world: &World) -> Vec<usize> {
+        (0..world.missions.len())
+            .filter(|&index| {
+                let mission = &world.missions[index];
+                mission.status == Status::Offered && mission.origin == self.system
+            })
+            .collect()
+    }
+
+    /// Lines listing the offered missions, followed by those of the player.
+    fn missions(&self, world: &World) -> Vec<String> {
+        let offers = self.offers(world);
+        let mut lines = vec!["Offered here:".to_string()];
+        if offers.is_empty() {
+            lines.push("  Nothing on offer.".to_string());
+        }
+        for (i, &index) in offers.iter().enumerate() {
+            let marker = if i == self.selected { ">" } else { " " };
+            lines.push(format!(
+                "{} {}",
+                marker,
+                world.missions[index].describe(world)
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Your missions:".to_string());
+        // Active missions first, then the latest outcomes.
+        let mut own: Vec<_> = world
+            .missions
+            .iter()
+            .filter(|mission| mission.status != Status::Offered)
+            .collect();
+        own.sort_by_key(|mission| mission.status != Status::Active);
+        for mission in own.iter().take(8) {
+            lines.push(format!(
+                "  {:?}: {}",
+                mission.status,
+                mission.describe(world)
+            ));
+        }
+        lines
+    }
+
+    /// Goods carried by the player's ship.
+    fn cargo(world: &World) -> String {
+        let ship = match world.player {
+            Some(player) => &world.ships[player],
+            None => return "none".to_string(),
+        };
+        let goods: Vec<_> = ship
+            .cargo
+            .goods()
+            .map(|(commodity, units)| format!("{} {:.0}", commodity.name(), units))
+            .collect();
+        if goods.is_empty() {
+            format!("empty of {:.0}", ship.capacity)
+        } else {
+            format!("{} of {:.0}", goods.join(", "), ship.capacity)
+        }
+    }
+
+    /// Lines describing the system itself, as far as the player knows it.
+    fn summary(&self, world: &World) -> Vec<String> {
+        let system = &world.systems[self.system];
+        let owner = system.faction.map_or("Unclaimed".to_string(), |faction| {
+            world.factions[faction].name.clone()
+        });
+        let prices = if world.knowledge(self.system) == Knowledge::Visited && system.population > 0
+        {
+            Commodity::ALL
+                .iter()
+                .map(|&commodity| {
+                    format!("{} {:.0}", commodity.name(), system.market.price(commodity))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else {
+            "unknown until visited".to_string()
+        };
+        vec![
+            format!("{} (class {:?} star)", system.name, system.star),
+            format!("Owner: {}, population {}", owner, system.population),
+            format!("Prices: {}", prices),
+            format!(
+                "Credits: {:.0}, cargo: {}",
+                world.credits,
+                Self::cargo(world)
+            ),
+        ]
+    }
+
+    /// Survey the selected body, or register or sell a claim on it.
+    /// Surveys draw from a stream derived from the world seed, the day and the body.
+    fn act(&mut self, action: Action, world: &mut World, seed: Seed) {
+        let (body, player) = match (self.body(world), world.player) {
+            (Some(body), Some(player)) => (body, player),
+            _ => return,
+        };
+        let result = match action {
+            Action::Survey => world
+                .survey(
+                    player,
+                    body,
+                    &mut seed
+                        .derive("survey")
+                        .index(world.day.0 as usize)
+                        .index(body)
+                        .rng(),
+                )
+                .map(|survey| format!("Surveyed, {} deposits found", survey.estimates.len())),
+            Action::Claim => world
+                .register_claim(body, Observer::Player)
+                .map(|_| "Claim registered".to_string()),
+            Action::Sell => world
+                .sell_claim(body)
+                .map(|price| format!("Claim sold for {:.0} credits", price)),
+            Action::Build => world
+                .build_operation(player, body)
+                .map(|kind| format!("{} built", kind.name())),
+            Action::Deliver => world.deliver(player).map(|(loaded, earned)| {
+                format!(
+                    "Loaded {:.0} units, sold cargo for {:.0} credits",
+                    loaded, earned
+                )
+            }),
+            _ => return,
+        };
+        self.message = result.unwrap_or_else(|e| e.to_string());
+    }
+}
+
+impl View for Details {
+    fn handle_event(&mut self, event: Event, ctx: &mut EventContext) -> Trans {
+        let count = if self.board {
+            self.offers(ctx.game_state()).len()
+        } else {
+            ctx.game_state().systems[self.system].bodies.len()
+        };
+        match ctx.action() {
+            Some(Action::Back) | Some(Action::Details) => return Trans::Pop,
+            Some(Action::ToggleOverlay) => {
+                self.board = !self.board;
+                self.selected = 0;
+            }
+            // Clicks select on the map beneath, so only accept on key presses.
+            Some(Action::Select) if self.board && matches!(event, Event::KeyDown { .. }) => {
+                let offer = self.offers(ctx.game_state()).get(self.selected).cloned();
+                if let (Some(offer), Some(player)) = (offer, ctx.game_state().player) {
+                    self.message = match ctx.game_state_mut().accept_mission(offer, player) {
+                        Ok(()) => "Mission accepted".to_string(),
+                        Err(e) => e.to_string(),
+                    };
+                    self.selected = 0;
+                }
+            }
+            Some(Action::PanUp) => self.selected = self.selected.saturating_sub(1),
+            Some(Action::PanDown) => {
+                self.selected = (self.selected + 1).min(count.saturating_sub(1))
+            }
+            Some(
+                action @ (Action::Survey
+                | Action::Claim
+                | Action::Sell
+                | Action::Build
+                | Action::Deliver),
+            ) if !self.board => {
+                let seed = Seed::new(ctx.slots().base().seed.into());
+                self.act(action, ctx.game_state_mut(), seed)
+            }
+            _ => {}
+        }
+        Trans::None
+    }
+
+    fn render(&self, ctx: &mut RenderContext) -> GameResult<()> {
+        let world = ctx.game_state();
+        let area = Self::area(ctx.camera().screen());
+
+        graphics::set_color(ctx.draw_state(), Color::new(0., 0., 0., 0.85))?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Fill, area)?;
+        graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Line(1.), area)?;
+
+        let mut lines = self.summary(world);
+        lines.push(String::new());
+        let bodies = &world.systems[self.system].bodies;
+        if self.board {
+            lines.extend(self.missions(world));
+        } else if bodies.is_empty() {
+            lines.push("No planets or belts.".to_string());
+        }
+        for (i, &body) in bodies.iter().enumerate().filter(|_| !self.board) {
+            lines.push(format!(
+                "{} {} at {:.2} AU: {}",
+                if i == self.selected { ">" } else { " " },
+                world.bodies[body].kind.name(),
+                world.bodies[body].orbit,
+                Self::survey(world, body)
+            ));
+        }
+        lines.push(String::new());
+        lines.push(self.message.clone());
+
+        for (row, line) in lines.iter().enumerate() {
+            let y = area.y + row as f32 * Self::ROW_HEIGHT + 2.;
+            ctx.draw_text(line, Point2::new(area.x + 5., y))?;
+        }
+        let help = if self.board {
+            "Up/Down: select mission, Return: accept, Tab: bodies"
+        } else {
+            "Up/Down: select body, V: survey, C: claim, X: sell claim, B: build, L: load and sell, Tab: missions"
+        };
+        ctx.draw_text(
+            help,
+            Point2::new(area.x + 5., area.bottom() - Self::ROW_HEIGHT),
+        )
+    }
+
+    fn transparent(&self) -> bool {
+        true
+    }
+}
@@ -12,9 +12,11 @@ impl Empty {
 
 impl View for Empty {
     /// Handles the given input in the view.
-    fn handle_event(&mut self, _: Event) -> Trans {
+    fn handle_event(&mut self, _: Event, _: &mut EventContext) -> Trans {
         Trans::None
     }
 
-    fn render(&self, _: &mut RenderContext) {}
+    fn render(&self, _: &mut RenderContext) -> GameResult<()> {
+        Ok(())
+    }
 }
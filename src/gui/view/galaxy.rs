@@ -0,0 +1,281 @@
+use super::*;
+
+use crate::{
+    entity::astronomical::{System, SystemIndex},
+    graph::LaneGraph,
+    gui::component::{
+        Component, Grid, Heatmap, Lanes, Legend, Minimap, Overlay, Ships, Territories, Trade,
+    },
+    knowledge::Knowledge,
+    spatial::SpatialIndex,
+};
+use ggez::graphics::{self, Color, DrawParam, Image, Point2, SpriteBatch};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// Map view of the whole galaxy.
+pub struct Galaxy {
+    index: Rc<SpatialIndex>,
+    legend: Legend,
+    minimap: Minimap,
+    ships: Ships,
+    graph: LaneGraph,
+    dragging: bool,
+    cursor: (i32, i32),
+    batch: RefCell<Option<SpriteBatch>>,
+}
+
+impl Galaxy {
+    /// Cell size in world units used for the spatial index.
+    const INDEX_CELL_SIZE: f64 = 10.;
+
+    /// Distance in pixels moved by a single pan action.
+    const PAN_STEP: f32 = 50.;
+
+    /// Zoom factor of a single zoom action.
+    const ZOOM_STEP: f64 = 1.25;
+
+    /// Zoom level below which systems are aggregated into density blobs.
+    const DENSITY_ZOOM: f64 = 0.5;
+
+    /// Size in pixels of the screen cells aggregated into one density blob.
+    const DENSITY_CELL: f32 = 12.;
+
+    /// Size in pixels of a single star sprite.
+    const STAR_SIZE: f32 = 3.;
+
+    /// Maximum distance in pixels from the cursor to pick a system.
+    const PICK_DISTANCE: f32 = 8.;
+
+    /// Create a new galaxy view over the given world.
+    pub fn new(world: &World) -> Self {
+        let mut index = SpatialIndex::new(Self::INDEX_CELL_SIZE);
+        for (id, system) in world.systems.iter().enumerate() {
+            index.insert(system.location, id);
+        }
+        let index = Rc::new(index);
+
+        let legend = Legend::new(vec![
+            Overlay::new("Density", Box::new(Heatmap::new(index.clone()))),
+            Overlay::new("Hyperlanes", Box::new(Lanes::new())),
+            Overlay::new("Trade", Box::new(Trade::new())),
+            Overlay::new("Territories", Box::new(Territories::new(index.clone()))),
+            Overlay::new("Distance grid", Box::new(Grid::new())),
+        ]);
+
+        Galaxy {
+            index,
+            legend,
+            minimap: Minimap::new(world),
+            ships: Ships::new(),
+            graph: LaneGraph::new(world),
+            dragging: false,
+            cursor: (0, 0),
+            batch: RefCell::new(None),
+        }
+    }
+
+    /// Returns the known system closest to the given screen position, if close enough.
+    fn system_at(&self, world: &World, camera: &Camera, x: f32, y: f32) -> Option<SystemIndex> {
+        self.index
+            .nearest(&camera.to_world(x, y))
+            .map(SystemIndex::from)
+            .filter(|&id| world.knowledge(id) != Knowledge::Unknown)
+            .filter(|id| {
+                let position = camera.to_screen(&world.systems[*id].location);
+                (position.x - x).hypot(position.y - y) <= Self::PICK_DISTANCE
+            })
+    }
+
+    /// Order the player's ship to travel to the given system.
+    /// Returns whether a route was found.
+    fn travel_to(&self, world: &mut World, target: SystemIndex) -> bool {
+        let player = match world.player {
+            Some(player) => player,
+            None => return false,
+        };
+
+        // Only plot routes through known systems.
+        let known = |system| world.knowledge(system) != Knowledge::Unknown;
+        match self.graph.plan(world, &world.ships[player], target, known) {
+            Ok(route) => {
+                info!(
+                    "Travelling to {} in {} legs",
+                    world.systems[target].name,
+                    route.len()
+                );
+                world.ships[player].order(route, target);
+                true
+            }
+            Err(e) => {
+                warn!("No route: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Returns all systems known by the player within the camera viewport.
+    fn visible<'a>(&self, world: &'a World, camera: &Camera) -> Vec<(&'a System, Knowledge)> {
+        let (min, max) = camera.bounds();
+        self.index
+            .query(min, max)
+            .into_iter()
+            .map(SystemIndex::from)
+            .map(|id| (&world.systems[id], world.knowledge(id)))
+            .filter(|&(_, knowledge)| knowledge != Knowledge::Unknown)
+            .collect()
+    }
+
+    /// Add each system as a single star sprite, dimmed if only detected.
+    fn add_stars(batch: &mut SpriteBatch, camera: &Camera, systems: &[(&System, Knowledge)]) {
+        let scale = Self::STAR_SIZE * (camera.zoom as f32).max(1.).sqrt();
+        for (system, knowledge) in systems {
+            let brightness = if *knowledge == Knowledge::Detected {
+                0.4
+            } else {
+                1.
+            };
+            batch.add(DrawParam {
+                dest: camera.to_screen(&system.location),
+                scale: Point2::new(scale, scale),
+                offset: Point2::new(0.5, 0.5),
+                color: Some(Color::new(1., 1., 1., brightness)),
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Aggregate systems into blobs sized and shaded by the number of systems
+    /// within each screen cell.
+    fn add_density(batch: &mut SpriteBatch, camera: &Camera, systems: &[(&System, Knowledge)]) {
+        let mut cells: HashMap<(i32, i32), u32> = HashMap::new();
+        for (system, _) in systems {
+            let pos = camera.to_screen(&system.location);
+            let cell = (
+                (pos.x / Self::DENSITY_CELL).floor() as i32,
+                (pos.y / Self::DENSITY_CELL).floor() as i32,
+            );
+            *cells.entry(cell).or_insert(0) += 1;
+        }
+
+        let max = cells.values().cloned().max().unwrap_or(1) as f32;
+        for ((x, y), count) in cells {
+            let density = count as f32 / max;
+            let scale = Self::DENSITY_CELL * density.sqrt().max(0.25);
+            batch.add(DrawParam {
+                dest: Point2::new(
+                    (x as f32 + 0.5) * Self::DENSITY_CELL,
+                    (y as f32 + 0.5) * Self::DENSITY_CELL,
+                ),
+                scale: Point2::new(scale, scale),
+                offset: Point2::new(0.5, 0.5),
+                color: Some(Color::new(1., 1., 1., 0.2 + 0.8 * density)),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+impl View for Galaxy {
+    fn handle_event(&mut self, event: Event, ctx: &mut EventContext) -> Trans {
+        if self.minimap.handle_event(&event, ctx.camera()) {
+            return Trans::None;
+        }
+
+        let (x, y) = (self.cursor.0 as f32, self.cursor.1 as f32);
+        match ctx.action() {
+            Some(Action::PanLeft) => ctx.camera().pan(-Self::PAN_STEP, 0.),
+            Some(Action::PanRight) => ctx.camera().pan(Self::PAN_STEP, 0.),
+            Some(Action::PanUp) => ctx.camera().pan(0., -Self::PAN_STEP),
+            Some(Action::PanDown) => ctx.camera().pan(0., Self::PAN_STEP),
+            Some(Action::ZoomIn) => ctx.camera().zoom_at(Self::ZOOM_STEP, x, y),
+            Some(Action::ZoomOut) => ctx.camera().zoom_at(1. / Self::ZOOM_STEP, x, y),
+            Some(Action::ToggleOverlay) => self.legend.visible = !self.legend.visible,
+            Some(Action::Search) => return Trans::Push(Box::new(Search::new())),
+            Some(Action::Outfit) => {
+                return Trans::Push(Box::new(Outfitting::new(ctx.game_state())));
+            }
+            Some(Action::Save) | Some(Action::Load) => {
+                return Trans::Push(Box::new(Saves::new(ctx.slots().list())));
+            }
+            Some(Action::History) => {
+                let camera = ctx.camera().clone();
+                let system = self
+                    .system_at(ctx.game_state(), &camera, x, y)
+                    .filter(|&id| ctx.game_state().knowledge(id) >= Knowledge::Surveyed);
+                return Trans::Push(Box::new(Chronicle::new(system)));
+            }
+            Some(Action::Details) => {
+                let camera = ctx.camera().clone();
+                let system = self
+                    .system_at(ctx.game_state(), &camera, x, y)
+                    .filter(|&id| ctx.game_state().knowledge(id) >= Knowledge::Surveyed);
+                if let Some(system) = system {
+                    return Trans::Push(Box::new(Details::new(system)));
+                }
+            }
+            Some(Action::Back) => return Trans::Quit,
+            _ => {}
+        }
+
+        match event {
+            Event::MouseDown { button, x, y, .. } if button != MouseButton::Right => {
+                if self.legend.click(x, y) {
+                    return Trans::None;
+                }
+                // Clicking a system orders a jump there, anywhere else drags the map.
+                let camera = ctx.camera().clone();
+                let target = self.system_at(ctx.game_state(), &camera, x as f32, y as f32);
+                let ordered = ctx.action() == Some(Action::Select)
+                    && target.is_some_and(|target| self.travel_to(ctx.game_state_mut(), target));
+                self.dragging = !ordered;
+            }
+            Event::KeyDown { .. } if ctx.action() == Some(Action::Select) => {
+                let camera = ctx.camera().clone();
+                if let Some(target) = self.system_at(ctx.game_state(), &camera, x, y) {
+                    self.travel_to(ctx.game_state_mut(), target);
+                }
+            }
+            Event::MouseUp { .. } | Event::FocusLost => self.dragging = false,
+            Event::MouseMove { x, y, xrel, yrel } => {
+                self.cursor = (x, y);
+                if self.dragging {
+                    ctx.camera().pan(-xrel as f32, -yrel as f32);
+                }
+            }
+            _ => {}
+        }
+        Trans::None
+    }
+
+    fn render(&self, ctx: &mut RenderContext) -> GameResult<()> {
+        let world = ctx.game_state();
+        let camera = ctx.camera();
+        let mut screen = camera.screen();
+        self.legend.render_overlays(&mut screen, ctx)?;
+
+        let systems = self.visible(world, camera);
+        let draw_ctx = ctx.draw_state();
+        let mut batch = self.batch.borrow_mut();
+        if batch.is_none() {
+            *batch = Some(SpriteBatch::new(Image::solid(
+                draw_ctx,
+                1,
+                graphics::WHITE,
+            )?));
+        }
+        let batch = batch.as_mut().unwrap();
+        batch.clear();
+
+        if camera.zoom < Self::DENSITY_ZOOM {
+            Self::add_density(batch, camera, &systems);
+        } else {
+            Self::add_stars(batch, camera, &systems);
+        }
+        graphics::set_color(draw_ctx, graphics::WHITE)?;
+        graphics::draw(draw_ctx, batch, Point2::origin(), 0.)?;
+
+        self.ships.render(&mut screen, ctx)?;
+        self.minimap.render(&mut screen, ctx)?;
+        self.legend.render(&mut screen, ctx)
+    }
+}
@@ -1,7 +1,19 @@
 use super::*;
 
+mod chronicle;
+mod details;
 mod empty;
+mod galaxy;
+mod outfitting;
+mod saves;
+mod search;
+use self::chronicle::Chronicle;
+use self::details::Details;
 use self::empty::Empty;
+pub use self::galaxy::Galaxy;
+use self::outfitting::Outfitting;
+use self::saves::Saves;
+use self::search::Search;
 
 /// Variants of UI state transtions (i.e switching between views).
 pub enum Trans {
@@ -25,11 +37,16 @@ impl StateMachine {
         }
     }
 
-    /// Handles the given event which can update the current view.
-    /// TODO: Handle quit events properly.
-    pub fn handle_event(&mut self, event: Event) {
-        match self.stack.last_mut().unwrap().handle_event(event) {
-            Trans::None => {}
+    /// Handles the given event which can update the current view, returning
+    /// whether it switched views.
+    /// Events are ignored once all views have been closed.
+    pub fn handle_event(&mut self, event: Event, ctx: &mut EventContext) -> bool {
+        let view = match self.stack.last_mut() {
+            Some(view) => view,
+            None => return false,
+        };
+        match view.handle_event(event, ctx) {
+            Trans::None => return false,
             Trans::Pop => {
                 self.stack.pop();
             }
@@ -40,12 +57,24 @@ impl StateMachine {
             }
             Trans::Quit => self.stack.clear(),
         }
+        true
     }
 
     /// The current view, if any.
     pub fn current(&self) -> Option<&Box<dyn View>> {
         self.stack.last()
     }
+
+    /// The views which should be rendered, bottom first.
+    /// Includes the current view and any views beneath transparent ones.
+    pub fn visible(&self) -> &[Box<dyn View>] {
+        let start = self
+            .stack
+            .iter()
+            .rposition(|view| !view.transparent())
+            .unwrap_or(0);
+        &self.stack[start..]
+    }
 }
 
 impl Default for StateMachine {
@@ -56,19 +85,35 @@ impl Default for StateMachine {
 
 pub trait View {
     /// Handles the given input in the view.
-    fn handle_event(&mut self, event: Event) -> Trans;
+    fn handle_event(&mut self, event: Event, ctx: &mut EventContext) -> Trans;
 
     /// Renders this view.
-    fn render(&self, ctx: &mut RenderContext);
+    fn render(&self, ctx: &mut RenderContext) -> GameResult<()>;
+
+    /// Whether views beneath this one should be rendered as well.
+    fn transparent(&self) -> bool {
+        false
+    }
+
+    /// Whether the view takes typed text, so that keys should not trigger
+    /// actions outside of it such as changing the game speed.
+    fn text_input(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        config,
+        gui::camera::Camera,
+        save::{Base, Slots},
+    };
 
     pub struct TestView {}
     impl View for TestView {
-        fn handle_event(&mut self, event: Event) -> Trans {
+        fn handle_event(&mut self, event: Event, _: &mut EventContext) -> Trans {
             match event {
                 Event::MouseDown { .. } => Trans::Push(Box::new(TestView {})),
                 Event::MouseUp { .. } => Trans::Pop,
@@ -76,33 +121,56 @@ mod tests {
             }
         }
 
-        fn render(&self, _: &mut RenderContext) {}
+        fn render(&self, _: &mut RenderContext) -> GameResult<()> {
+            Ok(())
+        }
     }
 
     #[test]
     fn test_state_pop() {
         let mut sm = StateMachine::new(Box::new(TestView {}));
+        let mut camera = Camera::new(RenderArea::new(0., 0., 800., 600.));
+        let mut world = World::new();
+        let slots = Slots::new(&config::Saves::default(), Base::default());
+        let mut ctx = EventContext::new(None, &mut world, &mut camera, &slots);
         assert!(sm.current().is_some());
 
-        sm.handle_event(Event::MouseDown {
-            button: MouseButton::Left,
-            x: 0,
-            y: 0,
-        });
+        assert!(sm.handle_event(
+            Event::MouseDown {
+                button: MouseButton::Left,
+                x: 0,
+                y: 0,
+                keymod: NOMOD,
+            },
+            &mut ctx,
+        ));
         assert!(sm.current().is_some());
+        assert!(!sm.handle_event(Event::FocusGained, &mut ctx));
 
         // Should be able to pop twice
-        sm.handle_event(Event::MouseUp {
-            button: MouseButton::Left,
-            x: 0,
-            y: 0,
-        });
+        sm.handle_event(
+            Event::MouseUp {
+                button: MouseButton::Left,
+                x: 0,
+                y: 0,
+                keymod: NOMOD,
+            },
+            &mut ctx,
+        );
         assert!(sm.current().is_some());
-        sm.handle_event(Event::MouseUp {
-            button: MouseButton::Left,
-            x: 0,
-            y: 0,
-        });
+        sm.handle_event(
+            Event::MouseUp {
+                button: MouseButton::Left,
+                x: 0,
+                y: 0,
+                keymod: NOMOD,
+            },
+            &mut ctx,
+        );
+        assert!(sm.current().is_none());
+
+        // Events after the last view is closed are ignored.
+        assert!(!sm.handle_event(Event::FocusGained, &mut ctx));
         assert!(sm.current().is_none());
     }
 }
@@ -0,0 +1,207 @@
+use super::*;
+
+use crate::outfitting::{Module, Outfit, Stats};
+use ggez::graphics::{self, Color, DrawMode, Point2};
+
+/// Overlay for refitting the player's ship, buying and selling modules at
+/// the station of the system it is stopped at.
+pub struct Outfitting {
+    selected: usize,
+    /// Grade of the module to fit to the selected slot, 0 for none.
+    grade: u8,
+    message: String,
+}
+
+impl Outfitting {
+    /// Height in pixels of each row.
+    const ROW_HEIGHT: f32 = 20.;
+
+    /// Width in pixels of the panel.
+    const WIDTH: f32 = 560.;
+
+    /// Margin in pixels around the panel.
+    const MARGIN: f32 = 40.;
+
+    /// Create a new view of the outfit of the player's ship.
+    pub fn new(world: &World) -> Self {
+        let mut outfitting = Outfitting {
+            selected: 0,
+            grade: 0,
+            message: String::new(),
+        };
+        outfitting.reset(world);
+        outfitting
+    }
+
+    /// Outfit of the player's ship, if it can be refitted.
+    fn outfit(world: &World) -> Option<&Outfit> {
+        world.ships.get(world.player?)?.outfit.as_ref()
+    }
+
+    /// Choose the module currently fitted to the selected slot.
+    fn reset(&mut self, world: &World) {
+        self.grade = Self::outfit(world)
+            .and_then(|outfit| outfit.modules.get(self.selected).cloned())
+            .and_then(|module| module.map(|module| module.grade))
+            .unwrap_or(0);
+    }
+
+    /// Module chosen for the selected slot, if any.
+    fn candidate(&self, outfit: &Outfit) -> Option<Module> {
+        let kind = *outfit.hull.slots.get(self.selected)?;
+        if self.grade == 0 {
+            None
+        } else {
+            Some(Module::new(kind, self.grade))
+        }
+    }
+
+    /// Area covered by the panel.
+    fn area(screen: RenderArea) -> RenderArea {
+        let width = Self::WIDTH.min(screen.w - 2. * Self::MARGIN);
+        RenderArea::new(
+            screen.x + (screen.w - width) / 2.,
+            screen.y + Self::MARGIN,
+            width,
+            screen.h - 2. * Self::MARGIN,
+        )
+    }
+
+    /// Lines comparing the stats of the ship with those after refitting.
+    fn compare(current: &Stats, refitted: &Stats) -> Vec<String> {
+        let stat = |name: &str, current: f64, refitted: f64| {
+            if (current - refitted).abs() < 1e-6 {
+                format!("{} {:.1}", name, current)
+            } else {
+                format!("{} {:.1} -> {:.1}", name, current, refitted)
+            }
+        };
+        vec![
+            format!(
+                "{}, {}",
+                stat("Speed", current.speed, refitted.speed),
+                stat("jump range", current.jump_range, refitted.jump_range)
+            ),
+            format!(
+                "{}, {}",
+                stat("Fuel", current.fuel_capacity, refitted.fuel_capacity),
+                stat("cargo", current.capacity, refitted.capacity)
+            ),
+            format!(
+                "{}, {}",
+                stat("Sensors", current.sensors, refitted.sensors),
+                stat("scanner", current.scanner, refitted.scanner)
+            ),
+            format!(
+                "{}, {}",
+                stat("Weapons", current.weapons, refitted.weapons),
+                stat("shields", current.shields, refitted.shields)
+            ),
+        ]
+    }
+
+    /// Lines describing the outfit and the chosen module.
+    fn describe(&self, world: &World, outfit: &Outfit) -> Vec<String> {
+        let stats = outfit.stats();
+        let mut lines = vec![
+            format!("Credits: {:.0}", world.credits),
+            format!(
+                "Power {:.0} of {:.0}, mass {:.0} of {:.0}",
+                stats.power, outfit.hull.power, stats.mass, outfit.hull.max_mass
+            ),
+            String::new(),
+        ];
+
+        let candidate = self.candidate(outfit);
+        for (slot, (&kind, module)) in outfit.hull.slots.iter().zip(&outfit.modules).enumerate() {
+            let fitted = module.map_or("empty".to_string(), Module::name);
+            let mut line = format!(
+                "{} {}: {}",
+                if slot == self.selected { ">" } else { " " },
+                kind.name(),
+                fitted
+            );
+            if slot == self.selected && candidate != *module {
+                let resale = module.map_or(0., |old| old.price() * Module::RESALE_FRACTION);
+                let cost = candidate.map_or(0., Module::price) - resale;
+                let name = candidate.map_or("empty".to_string(), Module::name);
+                line.push_str(&format!(" -> {} ({:+.0} credits)", name, -cost));
+            }
+            lines.push(line);
+        }
+
+        lines.push(String::new());
+        match outfit.refitted(self.selected, candidate) {
+            Ok(refitted) => lines.extend(Self::compare(&stats, &refitted.stats())),
+            Err(e) => {
+                lines.extend(Self::compare(&stats, &stats));
+                lines.push(format!("Can not fit: {}", e));
+            }
+        }
+        lines
+    }
+}
+
+impl View for Outfitting {
+    fn handle_event(&mut self, event: Event, ctx: &mut EventContext) -> Trans {
+        let slots = Self::outfit(ctx.game_state()).map_or(0, |outfit| outfit.modules.len());
+        match ctx.action() {
+            Some(Action::Back) | Some(Action::Outfit) => return Trans::Pop,
+            Some(Action::PanUp) => {
+                self.selected = self.selected.saturating_sub(1);
+                self.reset(ctx.game_state());
+            }
+            Some(Action::PanDown) => {
+                self.selected = (self.selected + 1).min(slots.saturating_sub(1));
+                self.reset(ctx.game_state());
+            }
+            Some(Action::PanLeft) => self.grade = self.grade.saturating_sub(1),
+            Some(Action::PanRight) => self.grade = (self.grade + 1).min(Module::MAX_GRADE),
+            // Clicks select on the map beneath, so only buy on key presses.
+            Some(Action::Select) if matches!(event, Event::KeyDown { .. }) => {
+                let world = ctx.game_state_mut();
+                let (player, candidate) = match (world.player, Self::outfit(world)) {
+                    (Some(player), Some(outfit)) => (player, self.candidate(outfit)),
+                    _ => return Trans::None,
+                };
+                self.message = match world.refit(player, self.selected, candidate) {
+                    Ok(cost) if cost >= 0. => format!("Refitted for {:.0} credits", cost),
+                    Ok(cost) => format!("Refitted, earning {:.0} credits", -cost),
+                    Err(e) => e.to_string(),
+                };
+            }
+            _ => {}
+        }
+        Trans::None
+    }
+
+    fn render(&self, ctx: &mut RenderContext) -> GameResult<()> {
+        let world = ctx.game_state();
+        let area = Self::area(ctx.camera().screen());
+
+        graphics::set_color(ctx.draw_state(), Color::new(0., 0., 0., 0.85))?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Fill, area)?;
+        graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Line(1.), area)?;
+
+        let mut lines = match Self::outfit(world) {
+            Some(outfit) => self.describe(world, outfit),
+            None => vec!["No ship to refit.".to_string()],
+        };
+        lines.push(String::new());
+        lines.push(self.message.clone());
+
+        for (row, line) in lines.iter().enumerate() {
+            let y = area.y + row as f32 * Self::ROW_HEIGHT + 2.;
+            ctx.draw_text(line, Point2::new(area.x + 5., y))?;
+        }
+        ctx.draw_text(
+            "Up/Down: select slot, Left/Right: choose module, Return: buy, Escape: close",
+            Point2::new(area.x + 5., area.bottom() - Self::ROW_HEIGHT),
+        )
+    }
+
+    fn transparent(&self) -> bool {
+        true
+    }
+}
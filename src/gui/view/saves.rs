@@ -0,0 +1,158 @@
+use super::*;
+
+use ggez::graphics::{self, Color, DrawMode, Point2};
+
+/// Overlay listing the save slots, saving to a new or existing slot or
+/// loading one.
+pub struct Saves {
+    /// Name typed for a new slot.
+    name: String,
+    slots: Vec<String>,
+    selected: usize,
+    message: String,
+}
+
+impl Saves {
+    /// Height in pixels of each row.
+    const ROW_HEIGHT: f32 = 20.;
+
+    /// Width in pixels of the panel.
+    const WIDTH: f32 = 400.;
+
+    /// Create a new view of the given slots.
+    pub fn new(slots: Vec<String>) -> Self {
+        Saves {
+            name: String::new(),
+            slots,
+            selected: 0,
+            message: String::new(),
+        }
+    }
+
+    /// Area covered by the panel, including the slots and help.
+    fn area(&self, screen: RenderArea) -> RenderArea {
+        RenderArea::new(
+            screen.x + (screen.w - Self::WIDTH) / 2.,
+            screen.y + 40.,
+            Self::WIDTH,
+            Self::ROW_HEIGHT * (self.slots.len() + 3) as f32,
+        )
+    }
+
+    /// Slot to save to, the typed name if any or else the selected slot.
+    fn target(&self) -> Option<&str> {
+        if self.name.is_empty() {
+            self.slots.get(self.selected).map(String::as_str)
+        } else {
+            Some(&self.name)
+        }
+    }
+}
+
+impl View for Saves {
+    fn handle_event(&mut self, event: Event, ctx: &mut EventContext) -> Trans {
+        match ctx.action() {
+            Some(Action::Back) => return Trans::Pop,
+            Some(Action::Select) if matches!(event, Event::KeyDown { .. }) => {
+                let result = match self.target() {
+                    Some(name) => ctx.slots().save(name, ctx.game_state()),
+                    None => return Trans::None,
+                };
+                match result {
+                    Ok(()) => return Trans::Pop,
+                    Err(e) => self.message = e.to_string(),
+                }
+            }
+            Some(Action::Load) => {
+                let name = match self.slots.get(self.selected) {
+                    Some(name) => name.clone(),
+                    None => return Trans::None,
+                };
+                match ctx.slots().load(&name, ctx.game_state_mut()) {
+                    Ok(()) => return Trans::Pop,
+                    Err(e) => self.message = e.to_string(),
+                }
+            }
+            _ => {}
+        }
+
+        match event {
+            Event::MouseDown { x, y, .. } => {
+                let area = self.area(ctx.camera().screen());
+                if !area.contains(Point2::new(x as f32, y as f32)) {
+                    return Trans::Pop;
+                }
+                let row = ((y as f32 - area.y) / Self::ROW_HEIGHT) as usize;
+                if row >= 1 && row <= self.slots.len() {
+                    self.selected = row - 1;
+                    self.name.clear();
+                }
+            }
+            Event::TextInput { text } => self.name.push_str(&text),
+            Event::KeyDown {
+                code: Keycode::Backspace,
+                ..
+            } => {
+                self.name.pop();
+            }
+            Event::KeyDown {
+                code: Keycode::Down,
+                ..
+            } if self.selected + 1 < self.slots.len() => {
+                self.selected += 1;
+                self.name.clear();
+            }
+            Event::KeyDown {
+                code: Keycode::Up, ..
+            } => {
+                self.selected = self.selected.saturating_sub(1);
+                self.name.clear();
+            }
+            _ => {}
+        }
+        Trans::None
+    }
+
+    fn render(&self, ctx: &mut RenderContext) -> GameResult<()> {
+        let area = self.area(ctx.camera().screen());
+
+        graphics::set_color(ctx.draw_state(), Color::new(0., 0., 0., 0.8))?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Fill, area)?;
+        graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Line(1.), area)?;
+        ctx.draw_text(
+            &format!("Save as: {}_", self.name),
+            Point2::new(area.x + 5., area.y + 2.),
+        )?;
+
+        for (row, name) in self.slots.iter().enumerate() {
+            let y = area.y + (row + 1) as f32 * Self::ROW_HEIGHT;
+            if row == self.selected && self.name.is_empty() {
+                graphics::set_color(ctx.draw_state(), Color::new(0.3, 0.5, 0.9, 0.6))?;
+                graphics::rectangle(
+                    ctx.draw_state(),
+                    DrawMode::Fill,
+                    RenderArea::new(area.x, y, area.w, Self::ROW_HEIGHT),
+                )?;
+                graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+            }
+            ctx.draw_text(name, Point2::new(area.x + 15., y + 2.))?;
+        }
+
+        let y = area.y + (self.slots.len() + 1) as f32 * Self::ROW_HEIGHT + 2.;
+        let help = if self.message.is_empty() {
+            "Return: save, F9: load selected, Escape: close"
+        } else {
+            &self.message
+        };
+        ctx.draw_text(help, Point2::new(area.x + 5., y))
+    }
+
+    fn transparent(&self) -> bool {
+        true
+    }
+
+    fn text_input(&self) -> bool {
+        true
+    }
+}
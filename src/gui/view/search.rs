@@ -0,0 +1,224 @@
+use super::*;
+
+use crate::{entity::astronomical::SystemIndex, knowledge::Knowledge};
+use ggez::graphics::{self, Color, DrawMode, Point2};
+
+/// Search box overlay, finding surveyed systems by fuzzy matching on their names.
+pub struct Search {
+    query: String,
+    results: Vec<SystemIndex>,
+    selected: usize,
+}
+
+impl Search {
+    /// Maximum number of results shown.
+    const MAX_RESULTS: usize = 10;
+
+    /// Height in pixels of each row.
+    const ROW_HEIGHT: f32 = 20.;
+
+    /// Width in pixels of the search box.
+    const WIDTH: f32 = 300.;
+
+    /// Zoom level used when centering the camera on the chosen system.
+    const FOCUS_ZOOM: f64 = 4.;
+
+    /// Create a new empty search.
+    pub fn new() -> Self {
+        Search {
+            query: String::new(),
+            results: vec![],
+            selected: 0,
+        }
+    }
+
+    /// Update the results to match the current query.
+    fn update(&mut self, world: &World) {
+        self.selected = 0;
+        if self.query.is_empty() {
+            self.results.clear();
+            return;
+        }
+
+        let mut matches: Vec<_> = world
+            .systems
+            .iter()
+            .enumerate()
+            .filter(|&(id, _)| world.knowledge(SystemIndex::from(id)) >= Knowledge::Surveyed)
+            .filter_map(|(id, system)| {
+                fuzzy_score(&self.query, &system.name).map(|score| (score, system.name.len(), id))
+            })
+            .collect();
+
+        // Best score first, shorter names first on ties.
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.results = matches
+            .into_iter()
+            .take(Self::MAX_RESULTS)
+            .map(|(_, _, id)| SystemIndex::from(id))
+            .collect();
+    }
+
+    /// Area covered by the search box, including the results.
+    fn area(&self, screen: RenderArea) -> RenderArea {
+        RenderArea::new(
+            screen.x + (screen.w - Self::WIDTH) / 2.,
+            screen.y + 40.,
+            Self::WIDTH,
+            Self::ROW_HEIGHT * (self.results.len() + 1) as f32,
+        )
+    }
+
+    /// Center the camera on the selected system and close the search.
+    fn choose(&self, ctx: &mut EventContext) -> Trans {
+        if let Some(id) = self.results.get(self.selected) {
+            let location = ctx.game_state().systems[*id].location;
+            let camera = ctx.camera();
+            camera.center = location;
+            camera.zoom = camera.zoom.max(Self::FOCUS_ZOOM);
+            Trans::Pop
+        } else {
+            Trans::None
+        }
+    }
+}
+
+/// Returns the score of matching the pattern against the text, if it matches at all.
+/// All pattern characters have to occur in order in the text, ignoring case.
+/// Consecutive characters and matches at the start of words score higher.
+fn fuzzy_score(pattern: &str, text: &str) -> Option<i32> {
+    let mut pattern = pattern.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut previous_matched = false;
+    let mut previous: Option<char> = None;
+
+    for c in text.chars() {
+        let next = match pattern.peek() {
+            Some(next) => *next,
+            None => break,
+        };
+        if c.to_lowercase().eq(Some(next)) {
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 10;
+            }
+            previous_matched = true;
+            pattern.next();
+        } else {
+            previous_matched = false;
+            score -= 1;
+        }
+        previous = Some(c);
+    }
+
+    if pattern.peek().is_none() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
+impl View for Search {
+    fn handle_event(&mut self, event: Event, ctx: &mut EventContext) -> Trans {
+        match (ctx.action(), &event) {
+            (Some(Action::Back), _) => return Trans::Pop,
+            (Some(Action::Select), Event::MouseDown { x, y, .. }) => {
+                let area = self.area(ctx.camera().screen());
+                if !area.contains(Point2::new(*x as f32, *y as f32)) {
+                    return Trans::Pop;
+                }
+                let row = ((*y as f32 - area.y) / Self::ROW_HEIGHT) as usize;
+                if row == 0 {
+                    return Trans::None;
+                }
+                self.selected = row - 1;
+                return self.choose(ctx);
+            }
+            (Some(Action::Select), _) => return self.choose(ctx),
+            _ => {}
+        }
+
+        match event {
+            Event::TextInput { text } => {
+                self.query.push_str(&text);
+                self.update(ctx.game_state());
+            }
+            Event::KeyDown {
+                code: Keycode::Backspace,
+                ..
+            } => {
+                self.query.pop();
+                self.update(ctx.game_state());
+            }
+            Event::KeyDown {
+                code: Keycode::Down,
+                ..
+            } if self.selected + 1 < self.results.len() => {
+                self.selected += 1;
+            }
+            Event::KeyDown {
+                code: Keycode::Up, ..
+            } => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            _ => {}
+        }
+        Trans::None
+    }
+
+    fn render(&self, ctx: &mut RenderContext) -> GameResult<()> {
+        let world = ctx.game_state();
+        let area = self.area(ctx.camera().screen());
+
+        graphics::set_color(ctx.draw_state(), Color::new(0., 0., 0., 0.8))?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Fill, area)?;
+        graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+        graphics::rectangle(ctx.draw_state(), DrawMode::Line(1.), area)?;
+        ctx.draw_text(
+            &format!("Search: {}_", self.query),
+            Point2::new(area.x + 5., area.y + 2.),
+        )?;
+
+        for (row, id) in self.results.iter().enumerate() {
+            let y = area.y + (row + 1) as f32 * Self::ROW_HEIGHT;
+            if row == self.selected {
+                graphics::set_color(ctx.draw_state(), Color::new(0.3, 0.5, 0.9, 0.6))?;
+                graphics::rectangle(
+                    ctx.draw_state(),
+                    DrawMode::Fill,
+                    RenderArea::new(area.x, y, area.w, Self::ROW_HEIGHT),
+                )?;
+                graphics::set_color(ctx.draw_state(), graphics::WHITE)?;
+            }
+            ctx.draw_text(&world.systems[*id].name, Point2::new(area.x + 15., y + 2.))?;
+        }
+        Ok(())
+    }
+
+    fn transparent(&self) -> bool {
+        true
+    }
+
+    fn text_input(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("sol", "Sol").is_some());
+        assert!(fuzzy_score("snl", "Sol").is_none());
+        assert!(fuzzy_score("", "Sol").is_some());
+
+        // Consecutive and word start matches rank higher.
+        assert!(fuzzy_score("cen", "Alpha Centauri") > fuzzy_score("cen", "Cygnus Menkar"));
+        assert!(fuzzy_score("ac", "Alpha Centauri") > fuzzy_score("ac", "Alcyone"));
+    }
+}
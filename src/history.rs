@@ -0,0 +1,267 @@
+use crate::{
+    calendar::Date,
+    entity::{astronomical::SystemIndex, faction::FactionIndex},
+    world::World,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Notable find made by the inhabitants of a system.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Discovery {
+    Ruins,
+    Derelict,
+    Anomaly,
+}
+
+/// Calamity striking the inhabitants of a system.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Disaster {
+    Plague,
+    Famine,
+    Quake,
+    Flare,
+}
+
+/// Something notable which happened in the world.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum EventKind {
+    Colonised {
+        system: SystemIndex,
+        faction: FactionIndex,
+    },
+    WarDeclared {
+        aggressor: FactionIndex,
+        defender: FactionIndex,
+    },
+    PeaceSigned {
+        factions: (FactionIndex, FactionIndex),
+    },
+    AllianceFormed {
+        factions: (FactionIndex, FactionIndex),
+    },
+    AllianceBroken {
+        factions: (FactionIndex, FactionIndex),
+    },
+    SystemConquered {
+        system: SystemIndex,
+        from: FactionIndex,
+        to: FactionIndex,
+    },
+    FactionDefeated {
+        faction: FactionIndex,
+    },
+    Discovered {
+        system: SystemIndex,
+        discovery: Discovery,
+    },
+    Disaster {
+        system: SystemIndex,
+        disaster: Disaster,
+        deaths: u64,
+    },
+    Renamed {
+        system: SystemIndex,
+        from: String,
+        to: String,
+    },
+}
+
+impl EventKind {
+    /// The system the event took place in, if any.
+    pub fn system(&self) -> Option<SystemIndex> {
+        match *self {
+            EventKind::Colonised { system, .. }
+            | EventKind::SystemConquered { system, .. }
+            | EventKind::Discovered { system, .. }
+            | EventKind::Disaster { system, .. }
+            | EventKind::Renamed { system, .. } => Some(system),
+            _ => None,
+        }
+    }
+
+    /// Describe the event in a sentence, using the current names of entities.
+    pub fn describe(&self, world: &World) -> String {
+        let system = |id: SystemIndex| system_name(world, id);
+        let faction = |id: FactionIndex| world.factions[id].name.clone();
+
+        match self {
+            EventKind::Colonised {
+                system: id,
+                faction: owner,
+            } => format!("The {} colonised {}.", faction(*owner), system(*id)),
+            EventKind::WarDeclared {
+                aggressor,
+                defender,
+            } => format!(
+                "The {} declared war on the {}.",
+                faction(*aggressor),
+                faction(*defender)
+            ),
+            EventKind::PeaceSigned { factions: (a, b) } => format!(
+                "The {} and the {} signed a peace treaty.",
+                faction(*a),
+                faction(*b)
+            ),
+            EventKind::AllianceFormed { factions: (a, b) } => {
+                format!(
+                    "The {} and the {} formed an alliance.",
+                    faction(*a),
+                    faction(*b)
+                )
+            }
+            EventKind::AllianceBroken { factions: (a, b) } => format!(
+                "The alliance between the {} and the {} broke apart.",
+                faction(*a),
+                faction(*b)
+            ),
+            EventKind::SystemConquered {
+                system: id,
+                from,
+                to,
+            } => format!(
+                "The {} conquered {} from the {}.",
+                faction(*to),
+                system(*id),
+                faction(*from)
+            ),
+            EventKind::FactionDefeated { faction: id } => {
+                format!("The {} lost its last system and fell.", faction(*id))
+            }
+            EventKind::Discovered {
+                system: id,
+                discovery,
+            } => {
+                let what = match discovery {
+                    Discovery::Ruins => "ancient ruins",
+                    Discovery::Derelict => "a derelict ship of unknown origin",
+                    Discovery::Anomaly => "a spatial anomaly",
+                };
+                format!("Explorers from {} discovered {}.", system(*id), what)
+            }
+            EventKind::Disaster {
+                system: id,
+                disaster,
+                deaths,
+            } => {
+                let what = match disaster {
+                    Disaster::Plague => "A plague",
+                    Disaster::Famine => "A famine",
+                    Disaster::Quake => "A planetary quake",
+                    Disaster::Flare => "A stellar flare",
+                };
+                format!(
+                    "{} struck {}, killing {} people.",
+                    what,
+                    system(*id),
+                    deaths
+                )
+            }
+            EventKind::Renamed { from, to, .. } => {
+                format!("{} was renamed {}.", capitalize(from), capitalize(to))
+            }
+        }
+    }
+}
+
+/// Event in the history of the world, dated by day since generation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistoryEvent {
+    pub day: Date,
+    pub kind: EventKind,
+}
+
+impl HistoryEvent {
+    /// Date of the event.
+    pub fn date(&self) -> Date {
+        self.day
+    }
+}
+
+/// Current name of the system, capitalized, or its number if it has no name.
+pub fn system_name(world: &World, system: SystemIndex) -> String {
+    let name = &world.systems[system].name;
+    if name.is_empty() {
+        format!("system #{}", Into::<usize>::into(system))
+    } else {
+        capitalize(name)
+    }
+}
+
+/// Render the history of the world as a Markdown chronicle, grouped by year.
+pub fn chronicle(world: &World) -> String {
+    let mut text = String::from("# Chronicle of the galaxy\n");
+    let mut year = None;
+    for event in &world.history {
+        let date = event.date();
+        if year != Some(date.year()) {
+            year = Some(date.year());
+            write!(text, "\n## Year {}\n\n", date.year()).unwrap();
+        }
+        writeln!(
+            text,
+            "- *Day {}:* {}",
+            date.day_of_year(),
+            event.kind.describe(world)
+        )
+        .unwrap();
+    }
+    text
+}
+
+/// Uppercase the first letter of the given name.
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entity::{astronomical::System, faction::Faction, faction::Government},
+        point::Point,
+    };
+
+    #[test]
+    fn test_chronicle() {
+        let mut world = World::new();
+        let system = world.systems.insert(
+            System::builder()
+                .location(Point::origin())
+                .name("vega")
+                .build(),
+        );
+        let faction = world.factions.insert(
+            Faction::builder()
+                .name("Vega Republic")
+                .color((0, 0, 0))
+                .government(Government::Democracy)
+                .home(system)
+                .build(),
+        );
+
+        world.day = Date(400);
+        world.record(EventKind::Colonised { system, faction });
+        world.day = Date(800);
+        world.record(EventKind::Disaster {
+            system,
+            disaster: Disaster::Plague,
+            deaths: 1000,
+        });
+
+        assert_eq!(world.history[0].date().to_string(), "Year 2, day 36");
+        assert_eq!(world.history[1].kind.system(), Some(system));
+        assert_eq!(
+            chronicle(&world),
+            "# Chronicle of the galaxy\n\
+             \n## Year 2\n\n\
+             - *Day 36:* The Vega Republic colonised Vega.\n\
+             \n## Year 3\n\n\
+             - *Day 71:* A plague struck Vega, killing 1000 people.\n"
+        );
+    }
+}
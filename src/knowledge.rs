@@ -0,0 +1,207 @@
+use crate::entity::{
+    astronomical::{Lane, System, SystemIndex},
+    faction::FactionIndex,
+    ship::Ship,
+    EntityArray,
+};
+use crate::point::Point;
+use serde::{Deserialize, Serialize};
+
+/// How much an observer knows about a system, least first.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+pub enum Knowledge {
+    /// Nothing is known, not even that the system exists.
+    #[default]
+    Unknown,
+    /// Location is known, e.g. from long range sensors.
+    Detected,
+    /// Name, star, bodies and owner are known.
+    Surveyed,
+    /// Visited in person, everything is known.
+    Visited,
+}
+
+/// Someone gathering knowledge about the galaxy.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Observer {
+    Player,
+    Faction(FactionIndex),
+}
+
+/// Knowledge of a single observer about every system.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Chart {
+    pub observer: Observer,
+    systems: Vec<Knowledge>,
+}
+
+impl Chart {
+    /// Fraction of the sensor range within which systems are surveyed rather than only detected.
+    pub const SURVEY_FRACTION: f64 = 0.25;
+
+    /// Create a new chart where no system is known.
+    pub fn new(observer: Observer, systems: usize) -> Self {
+        Chart {
+            observer,
+            systems: vec![Knowledge::Unknown; systems],
+        }
+    }
+
+    /// Knowledge about the given system.
+    pub fn get(&self, system: SystemIndex) -> Knowledge {
+        let system: usize = system.into();
+        self.systems.get(system).cloned().unwrap_or_default()
+    }
+
+    /// Raise the knowledge about the given system, never lowering it.
+    /// Returns whether anything new was learned.
+    pub fn learn(&mut self, system: SystemIndex, knowledge: Knowledge) -> bool {
+        let system: usize = system.into();
+        match self.systems.get_mut(system) {
+            Some(known) if *known < knowledge => {
+                *known = knowledge;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Number of systems known at least to the given degree.
+    pub fn count(&self, knowledge: Knowledge) -> usize {
+        self.systems
+            .iter()
+            .filter(|&&known| known >= knowledge)
+            .count()
+    }
+
+    /// Visit the given system, surveying the systems connected to it by lanes.
+    /// Returns whether it was visited for the first time.
+    pub fn visit(&mut self, system: SystemIndex, lanes: &EntityArray<Lane>) -> bool {
+        if !self.learn(system, Knowledge::Visited) {
+            return false;
+        }
+        for other in lanes.iter().filter_map(|lane| lane.other(system)) {
+            self.learn(other, Knowledge::Surveyed);
+        }
+        true
+    }
+
+    /// Learn what sensors of the given range can see from the location.
+    pub fn sweep(&mut self, location: &Point, sensors: f64, systems: &EntityArray<System>) {
+        for (id, system) in systems.iter().enumerate() {
+            let distance = system.location.distance(location);
+            if distance <= sensors * Chart::SURVEY_FRACTION {
+                self.learn(SystemIndex::from(id), Knowledge::Surveyed);
+            } else if distance <= sensors {
+                self.learn(SystemIndex::from(id), Knowledge::Detected);
+            }
+        }
+    }
+
+    /// Learn what the ship can see with its sensors from its current location.
+    pub fn observe(
+        &mut self,
+        ship: &Ship,
+        systems: &EntityArray<System>,
+        lanes: &EntityArray<Lane>,
+    ) {
+        // Nothing new can be seen from a system the ship has already swept.
+        if !ship.travelling() && !self.visit(ship.system, lanes) {
+            return;
+        }
+        self.sweep(&ship.location, ship.sensors, systems);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entity::ship::Leg, world::World};
+
+    #[test]
+    fn test_observe() {
+        let mut systems = EntityArray::new();
+        let mut lanes = EntityArray::new();
+        for x in &[0., 5., 20., 100., 200.] {
+            systems.insert(
+                System::builder()
+                    .location(Point::new(*x, 0.))
+                    .name("")
+                    .build(),
+            );
+        }
+        lanes.insert(Lane::new(SystemIndex::from(0), SystemIndex::from(4), 200.));
+
+        let ship = Ship::builder()
+            .name("Test")
+            .location(Point::origin())
+            .system(SystemIndex::from(0))
+            .speed(1.)
+            .fuel(1.)
+            .sensors(40.)
+            .build();
+        let mut chart = Chart::new(Observer::Player, systems.len());
+        chart.observe(&ship, &systems, &lanes);
+
+        let known: Vec<_> = (0..systems.len())
+            .map(|id| chart.get(SystemIndex::from(id)))
+            .collect();
+        assert_eq!(
+            known,
+            vec![
+                Knowledge::Visited,
+                Knowledge::Surveyed,
+                Knowledge::Detected,
+                Knowledge::Unknown,
+                Knowledge::Surveyed,
+            ]
+        );
+        assert_eq!(chart.count(Knowledge::Detected), 4);
+
+        // Knowledge is never lost.
+        assert!(!chart.learn(SystemIndex::from(0), Knowledge::Detected));
+        assert_eq!(chart.get(SystemIndex::from(0)), Knowledge::Visited);
+    }
+
+    #[test]
+    fn test_travel() {
+        let mut world = World::new();
+        for x in &[0., 10., 20.] {
+            world.systems.insert(
+                System::builder()
+                    .location(Point::new(*x, 0.))
+                    .name("")
+                    .build(),
+            );
+        }
+        let route = (0..2)
+            .map(|from| {
+                let (from, to) = (SystemIndex::from(from), SystemIndex::from(from + 1));
+                Leg::Lane(world.lanes.insert(Lane::new(from, to, 10.)))
+            })
+            .collect();
+        world
+            .charts
+            .push(Chart::new(Observer::Player, world.systems.len()));
+        let mut ship = Ship::builder()
+            .name("Test")
+            .location(Point::origin())
+            .system(SystemIndex::from(0))
+            .speed(10.)
+            .fuel(10.)
+            .observer(Some(Observer::Player))
+            .build();
+        ship.order(route, SystemIndex::from(2));
+        world.ships.insert(ship);
+
+        // Systems passed through within a single step are visited too.
+        world.travel(2.);
+        world.explore();
+        let chart = world.chart(Observer::Player).unwrap();
+        assert_eq!(chart.get(SystemIndex::from(1)), Knowledge::Visited);
+        assert_eq!(chart.get(SystemIndex::from(2)), Knowledge::Visited);
+        assert_eq!(chart.get(SystemIndex::from(0)), Knowledge::Surveyed);
+    }
+}
@@ -1,12 +1,27 @@
 pub use failure::Error;
 pub use log::{debug, info, warn};
-use std::{fs::File, io};
+use std::{
+    env,
+    fs::{self, File},
+    io,
+};
 
+pub mod calendar;
 pub mod config;
+pub mod economy;
 pub mod entity;
 pub mod gen;
+pub mod graph;
 mod gui;
+pub mod history;
+pub mod knowledge;
+pub mod mining;
+pub mod missions;
+pub mod outfitting;
 pub mod point;
+pub mod prospecting;
+pub mod save;
+pub mod spatial;
 pub mod world;
 
 /// Configure logging to file and stdout.
@@ -43,25 +58,46 @@ fn main() -> Result<(), Error> {
     setup_logging()?;
 
     // Load config on compile.
-    let config: config::Config = toml::from_str(include_str!("../Config.toml"))
+    let mut config: config::Config = toml::from_str(include_str!("../Config.toml"))
         .expect("Failed to load config file Config.toml");
     debug!("Using configuration: {:#?}", config);
 
+    // Continue from the save slot given on the command line, if any, which
+    // requires regenerating the world it was saved from.
+    let save = match env::args().nth(1) {
+        Some(slot) => Some(save::read(&config.saves, &slot)?),
+        None => None,
+    };
+    if let Some(ref save) = save {
+        if save.base.seed != config.simulation.map_seed {
+            info!("Using map seed {} of the save", save.base.seed);
+            config.simulation.map_seed = save.base.seed;
+        }
+    }
+
     // Create world object.
     info!("Creating world object.");
     let mut world = world::World::new();
 
-    gen::generate(&config, &mut world);
-    gen::simulate(&config, &mut world);
-    gen::finalize(&config, &mut world);
+    // Generate and simulate the world using the configured stages.
+    gen::Pipeline::from_config(&config)?.run(&config, &mut world);
 
     // Write final world object to file.
     info!("Initial simulation done, writing world object to world.json");
     let f = File::create("world.json")?;
     serde_json::to_writer_pretty(f, &world)?;
 
+    info!("Writing the history of the world to chronicle.md");
+    fs::write("chronicle.md", history::chronicle(&world))?;
+
+    let base = save::Base::new(config.simulation.map_seed, &world)?;
+    if let Some(save) = save {
+        info!("Restoring saved game from {}", save.day);
+        save.restore(&base, &mut world)?;
+    }
+
     info!("Starting GUI...");
-    let mut gui = gui::GUI::new(world);
+    let mut gui = gui::GUI::new(world, base, &config);
     gui.start();
 
     Ok(())
@@ -0,0 +1,150 @@
+use crate::{
+    calendar::Date,
+    config,
+    economy::Cargo,
+    entity::astronomical::{BodyIndex, BodyKind},
+    knowledge::Observer,
+    prospecting::Deposit,
+};
+use serde::{Deserialize, Serialize};
+
+/// Kind of operation extracting the deposits of a body.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationKind {
+    /// Mining outpost on the surface of a planet.
+    Outpost,
+    /// Ship harvesting a gas giant or an asteroid belt.
+    Harvester,
+}
+
+impl OperationKind {
+    /// Kind of operation suited to the kind of body.
+    pub fn for_body(kind: BodyKind) -> Self {
+        match kind {
+            BodyKind::GasGiant | BodyKind::Belt => OperationKind::Harvester,
+            BodyKind::Rocky | BodyKind::Ocean | BodyKind::Ice => OperationKind::Outpost,
+        }
+    }
+
+    /// Credits paid to set up the operation.
+    pub fn cost(self) -> f64 {
+        match self {
+            OperationKind::Outpost => 500.,
+            OperationKind::Harvester => 300.,
+        }
+    }
+
+    /// Credits paid per day to keep the operation running.
+    pub fn upkeep(self) -> f64 {
+        match self {
+            OperationKind::Outpost => 2.,
+            OperationKind::Harvester => 4.,
+        }
+    }
+
+    /// Units extracted per day from a full deposit.
+    pub fn base_yield(self, config: &config::Mining) -> f64 {
+        match self {
+            OperationKind::Outpost => config.outpost_yield,
+            OperationKind::Harvester => config.harvester_yield,
+        }
+    }
+
+    /// Human readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            OperationKind::Outpost => "Outpost",
+            OperationKind::Harvester => "Harvester",
+        }
+    }
+}
+
+/// Operation extracting the deposits of a claimed body.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Operation {
+    pub kind: OperationKind,
+    pub body: BodyIndex,
+    pub owner: Observer,
+    /// Day the operation was set up.
+    pub built: Date,
+    /// Extracted units waiting to be collected.
+    pub stock: Cargo,
+}
+
+impl Operation {
+    /// Units per day extracted from the deposit, following the depletion curve.
+    pub fn yield_rate(&self, deposit: &Deposit, config: &config::Mining) -> f64 {
+        let remaining = deposit.amount / deposit.initial.max(1.);
+        if remaining <= config.exhausted_fraction {
+            0.
+        } else {
+            self.kind.base_yield(config) * remaining.powf(config.depletion_exponent)
+        }
+    }
+
+    /// Whether every deposit worked by the operation is exhausted.
+    pub fn exhausted(&self, deposits: &[Deposit], config: &config::Mining) -> bool {
+        deposits
+            .iter()
+            .all(|deposit| self.yield_rate(deposit, config) <= 0.)
+    }
+
+    /// Extract from the deposits for the given number of days, until storage is full.
+    /// Returns the units extracted.
+    pub fn extract(&mut self, deposits: &mut [Deposit], config: &config::Mining, days: f64) -> f64 {
+        let mut extracted = 0.;
+        for deposit in deposits.iter_mut() {
+            let space = (config.storage - self.stock.total()).max(0.);
+            let units = (self.yield_rate(deposit, config) * days)
+                .min(deposit.amount)
+                .min(space);
+            if units > 0. {
+                deposit.amount -= units;
+                self.stock.add(deposit.commodity, units);
+                extracted += units;
+            }
+        }
+        extracted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::economy::Commodity;
+
+    #[test]
+    fn test_extract() {
+        let config = config::Mining::default();
+        let mut deposits = vec![Deposit {
+            commodity: Commodity::Ore,
+            amount: 1000.,
+            initial: 1000.,
+            depth: 0.5,
+        }];
+        let mut operation = Operation {
+            kind: OperationKind::Outpost,
+            body: BodyIndex::from(0),
+            owner: Observer::Player,
+            built: Date::default(),
+            stock: Cargo::default(),
+        };
+
+        // Full yield from a full deposit.
+        let extracted = operation.extract(&mut deposits, &config, 1.);
+        assert_eq!(extracted, config.outpost_yield);
+        assert_eq!(operation.stock.get(Commodity::Ore), config.outpost_yield);
+        assert_eq!(deposits[0].amount, 1000. - config.outpost_yield);
+
+        // Yield falls as the deposit depletes, until it is exhausted.
+        let mut previous = extracted;
+        for _ in 0..1_000 {
+            let extracted = operation.extract(&mut deposits, &config, 1.);
+            assert!(extracted <= previous);
+            previous = extracted;
+        }
+        assert!(operation.exhausted(&deposits, &config));
+        assert!(deposits[0].amount > 0.);
+        assert!(operation.stock.total() <= config.storage);
+    }
+}
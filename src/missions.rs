@@ -0,0 +1,105 @@
+use crate::{
+    calendar::Date,
+    economy::Commodity,
+    entity::{astronomical::SystemIndex, faction::FactionIndex, ship::ShipIndex},
+    history,
+    world::World,
+};
+use serde::{Deserialize, Serialize};
+
+/// What has to be done to complete a mission.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Objective {
+    /// Carry goods, loaded on acceptance, to the destination.
+    Deliver {
+        commodity: Commodity,
+        units: f64,
+        destination: SystemIndex,
+    },
+    /// Survey every system within the radius of the center.
+    Survey { center: SystemIndex, radius: f64 },
+    /// Stay close to a convoy until it reaches the destination.
+    Escort {
+        destination: SystemIndex,
+        /// Convoy ship, launched on acceptance.
+        convoy: Option<ShipIndex>,
+    },
+    /// Find a ship lost somewhere within the radius of the hint.
+    Find {
+        lost: SystemIndex,
+        hint: SystemIndex,
+        radius: f64,
+    },
+}
+
+/// Progress of a mission.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// On the board of the origin, waiting to be accepted.
+    Offered,
+    Active,
+    Completed,
+    Failed,
+}
+
+/// Contract offered on the board of a system.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Mission {
+    pub objective: Objective,
+    /// System on whose board the mission is offered.
+    pub origin: SystemIndex,
+    /// Faction offering the mission, if any.
+    pub issuer: Option<FactionIndex>,
+    /// Credits paid on completion.
+    pub reward: f64,
+    /// Last day to complete the mission, or to accept it while offered.
+    pub deadline: Date,
+    pub status: Status,
+}
+
+impl Mission {
+    /// Furthest distance from the convoy at which it counts as escorted.
+    pub const ESCORT_DISTANCE: f64 = 5.;
+
+    /// Speed of a convoy relative to the ship escorting it.
+    pub const CONVOY_SPEED: f64 = 0.8;
+
+    /// Human readable description of the mission.
+    pub fn describe(&self, world: &World) -> String {
+        let name = |system: SystemIndex| history::system_name(world, system);
+        let task = match self.objective {
+            Objective::Deliver {
+                commodity,
+                units,
+                destination,
+            } => format!(
+                "Deliver {:.0} {} to {}",
+                units,
+                commodity.name(),
+                name(destination)
+            ),
+            Objective::Survey { center, radius } => {
+                format!(
+                    "Survey all systems within {:.0} of {}",
+                    radius,
+                    name(center)
+                )
+            }
+            Objective::Escort { destination, .. } => {
+                format!("Escort a convoy to {}", name(destination))
+            }
+            Objective::Find { hint, radius, .. } => {
+                format!("Find a ship lost within {:.0} of {}", radius, name(hint))
+            }
+        };
+        let deadline = self.deadline;
+        let due = match self.status {
+            Status::Offered | Status::Active => format!(" (in {})", world.date().until(deadline)),
+            Status::Completed | Status::Failed => String::new(),
+        };
+        format!(
+            "{} by {}{} for {:.0} credits",
+            task, deadline, due, self.reward
+        )
+    }
+}
@@ -0,0 +1,330 @@
+use failure::{format_err, Error};
+use serde::{Deserialize, Serialize};
+
+/// Kind of ship module, each fitting only the hull slots of its kind.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleKind {
+    /// Engine moving the ship, along lanes and in jumps.
+    Drive,
+    FuelTank,
+    CargoHold,
+    /// Sensors detecting systems and scanner surveying bodies.
+    Scanner,
+    Weapon,
+    Shield,
+}
+
+impl ModuleKind {
+    /// Mass of the module at the lowest grade.
+    fn mass(self) -> f64 {
+        match self {
+            ModuleKind::Drive => 20.,
+            ModuleKind::FuelTank => 10.,
+            ModuleKind::CargoHold => 15.,
+            ModuleKind::Scanner => 5.,
+            ModuleKind::Weapon => 10.,
+            ModuleKind::Shield => 15.,
+        }
+    }
+
+    /// Power drawn by the module at the lowest grade.
+    fn power(self) -> f64 {
+        match self {
+            ModuleKind::Drive => 5.,
+            ModuleKind::FuelTank | ModuleKind::CargoHold => 0.,
+            ModuleKind::Scanner => 3.,
+            ModuleKind::Weapon => 4.,
+            ModuleKind::Shield => 5.,
+        }
+    }
+
+    /// Credits paid for the module at the lowest grade.
+    fn price(self) -> f64 {
+        match self {
+            ModuleKind::Drive => 800.,
+            ModuleKind::FuelTank => 200.,
+            ModuleKind::CargoHold => 300.,
+            ModuleKind::Scanner => 600.,
+            ModuleKind::Weapon | ModuleKind::Shield => 700.,
+        }
+    }
+
+    /// Human readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            ModuleKind::Drive => "Drive",
+            ModuleKind::FuelTank => "Fuel tank",
+            ModuleKind::CargoHold => "Cargo hold",
+            ModuleKind::Scanner => "Scanner",
+            ModuleKind::Weapon => "Weapon",
+            ModuleKind::Shield => "Shield",
+        }
+    }
+}
+
+/// Module fitted to a ship, performing better but heavier, hungrier and
+/// pricier the higher its grade.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Module {
+    pub kind: ModuleKind,
+    /// Grade from 1 to `MAX_GRADE`.
+    pub grade: u8,
+}
+
+impl Module {
+    /// Highest grade of modules on sale.
+    pub const MAX_GRADE: u8 = 3;
+
+    /// Fraction of the price paid back when selling a module.
+    pub const RESALE_FRACTION: f64 = 0.5;
+
+    /// Create a new module of the given kind and grade.
+    pub fn new(kind: ModuleKind, grade: u8) -> Self {
+        Module { kind, grade }
+    }
+
+    /// Mass added to the ship.
+    pub fn mass(self) -> f64 {
+        self.kind.mass() * (1. + 0.5 * f64::from(self.grade.saturating_sub(1)))
+    }
+
+    /// Power drawn from the reactor of the hull.
+    pub fn power(self) -> f64 {
+        self.kind.power() * f64::from(self.grade)
+    }
+
+    /// Credits paid for a new module, doubling with each grade.
+    /// Grade 0, which is rejected when fitting, is priced as grade 1.
+    pub fn price(self) -> f64 {
+        self.kind.price() * 2f64.powi(i32::from(self.grade.saturating_sub(1)))
+    }
+
+    /// Human readable name.
+    pub fn name(self) -> String {
+        format!("{} mk {}", self.kind.name(), self.grade)
+    }
+}
+
+/// Frame of a ship, with a slot for each module it can be fitted with.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Hull {
+    /// Mass of the empty hull.
+    pub mass: f64,
+    /// Most total mass the frame can carry, including the hull itself.
+    pub max_mass: f64,
+    /// Power supplied by the reactor to the modules.
+    pub power: f64,
+    /// Kind of module fitting each slot.
+    pub slots: Vec<ModuleKind>,
+}
+
+/// Performance of a ship, derived from its outfit.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub mass: f64,
+    /// Power drawn by all modules.
+    pub power: f64,
+    /// Distance travelled per day.
+    pub speed: f64,
+    pub jump_range: f64,
+    pub fuel_capacity: f64,
+    /// Units of goods which can be carried.
+    pub capacity: f64,
+    /// Range within which systems are detected.
+    pub sensors: f64,
+    /// Quality of surveys, from 0 to 1.
+    pub scanner: f64,
+    pub weapons: f64,
+    pub shields: f64,
+}
+
+/// Hull of a ship together with the modules fitted to its slots.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Outfit {
+    pub hull: Hull,
+    /// Module fitted to each slot of the hull, if any.
+    pub modules: Vec<Option<Module>>,
+}
+
+impl Outfit {
+    /// Thrust of a drive per grade, giving the speed divided by the total mass.
+    const THRUST: f64 = 600.;
+
+    /// Jump capability of a drive per grade, giving the jump range divided by
+    /// the total mass.
+    const JUMP: f64 = 900.;
+
+    /// Fuel held by a tank per grade.
+    const FUEL: f64 = 15.;
+
+    /// Units of goods held by a cargo hold per grade.
+    const CARGO: f64 = 250.;
+
+    /// Sensor range of a scanner, per grade plus one.
+    const SENSORS: f64 = 20.;
+
+    /// Rating of weapons and shields per grade.
+    const RATING: f64 = 10.;
+
+    /// Create a new outfit of the hull with the given modules, which is not
+    /// necessarily valid.
+    pub fn new(hull: Hull, modules: Vec<Option<Module>>) -> Self {
+        Outfit { hull, modules }
+    }
+
+    /// The fitted modules.
+    fn fitted(&self) -> impl Iterator<Item = Module> + '_ {
+        self.modules.iter().filter_map(|module| *module)
+    }
+
+    /// Performance of a ship fitted with the outfit.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            mass: self.hull.mass,
+            ..Default::default()
+        };
+        let (mut thrust, mut jump) = (0., 0.);
+        for module in self.fitted() {
+            let grade = f64::from(module.grade);
+            stats.mass += module.mass();
+            stats.power += module.power();
+            match module.kind {
+                ModuleKind::Drive => {
+                    thrust += Self::THRUST * grade;
+                    jump += Self::JUMP * grade;
+                }
+                ModuleKind::FuelTank => stats.fuel_capacity += Self::FUEL * grade,
+                ModuleKind::CargoHold => stats.capacity += Self::CARGO * grade,
+                // Only the best scanner is used.
+                ModuleKind::Scanner => {
+                    stats.sensors = stats.sensors.max(Self::SENSORS * (grade + 1.));
+                    stats.scanner = stats.scanner.max(0.2 * (grade + 1.)).min(1.);
+                }
+                ModuleKind::Weapon => stats.weapons += Self::RATING * grade,
+                ModuleKind::Shield => stats.shields += Self::RATING * grade,
+            }
+        }
+        stats.speed = thrust / stats.mass;
+        stats.jump_range = jump / stats.mass;
+        stats
+    }
+
+    /// Check that the modules fit the slots of the hull, and stay within its
+    /// power and mass budgets.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.modules.len() != self.hull.slots.len() {
+            return Err(format_err!(
+                "{} modules for {} slots",
+                self.modules.len(),
+                self.hull.slots.len()
+            ));
+        }
+        for (module, &slot) in self.modules.iter().zip(&self.hull.slots) {
+            match module {
+                Some(module) if module.kind != slot => {
+                    return Err(format_err!(
+                        "{} does not fit a {} slot",
+                        module.name(),
+                        slot.name().to_lowercase()
+                    ));
+                }
+                Some(module) if module.grade == 0 || module.grade > Module::MAX_GRADE => {
+                    return Err(format_err!("No {} of grade {}", slot.name(), module.grade));
+                }
+                _ => {}
+            }
+        }
+        if !self.fitted().any(|module| module.kind == ModuleKind::Drive) {
+            return Err(format_err!("A drive is needed to fly"));
+        }
+
+        let stats = self.stats();
+        if stats.power > self.hull.power {
+            return Err(format_err!(
+                "Modules draw {:.0} power, but the reactor supplies {:.0}",
+                stats.power,
+                self.hull.power
+            ));
+        }
+        if stats.mass > self.hull.max_mass {
+            return Err(format_err!(
+                "Mass of {:.0} is more than the hull carries, {:.0}",
+                stats.mass,
+                self.hull.max_mass
+            ));
+        }
+        Ok(())
+    }
+
+    /// The outfit with the given slot refitted, if still valid.
+    pub fn refitted(&self, slot: usize, module: Option<Module>) -> Result<Outfit, Error> {
+        if slot >= self.modules.len() {
+            return Err(format_err!("No slot {}", slot));
+        }
+        let mut outfit = self.clone();
+        outfit.modules[slot] = module;
+        outfit.validate()?;
+        Ok(outfit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outfit() -> Outfit {
+        let hull = Hull {
+            mass: 40.,
+            max_mass: 100.,
+            power: 10.,
+            slots: vec![ModuleKind::Drive, ModuleKind::FuelTank, ModuleKind::Weapon],
+        };
+        Outfit::new(
+            hull,
+            vec![
+                Some(Module::new(ModuleKind::Drive, 1)),
+                Some(Module::new(ModuleKind::FuelTank, 2)),
+                None,
+            ],
+        )
+    }
+
+    #[test]
+    fn test_stats() {
+        let outfit = outfit();
+        assert!(outfit.validate().is_ok());
+        let stats = outfit.stats();
+        assert_eq!(stats.mass, 75.);
+        assert_eq!(stats.speed, 8.);
+        assert_eq!(stats.jump_range, 12.);
+        assert_eq!(stats.fuel_capacity, 30.);
+
+        // Heavier modules slow the ship down.
+        let armed = outfit
+            .refitted(2, Some(Module::new(ModuleKind::Weapon, 1)))
+            .unwrap();
+        assert!(armed.stats().speed < stats.speed);
+        assert_eq!(armed.stats().weapons, 10.);
+    }
+
+    #[test]
+    fn test_validate() {
+        let outfit = outfit();
+        assert!(outfit
+            .refitted(2, Some(Module::new(ModuleKind::Shield, 1)))
+            .is_err());
+        assert!(outfit.refitted(0, None).is_err());
+        assert!(outfit.refitted(3, None).is_err());
+
+        // Over the power budget of the reactor.
+        let hungry = outfit.refitted(2, Some(Module::new(ModuleKind::Weapon, 2)));
+        assert!(hungry.unwrap_err().to_string().contains("power"));
+
+        // Grades out of range are rejected, without overflowing their stats.
+        for grade in &[0, Module::MAX_GRADE + 1, u8::MAX] {
+            let module = Module::new(ModuleKind::FuelTank, *grade);
+            assert!(module.mass() > 0. && module.price() > 0.);
+            assert!(outfit.refitted(1, Some(module)).is_err());
+        }
+    }
+}
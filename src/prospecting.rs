@@ -0,0 +1,130 @@
+use crate::{
+    calendar::Date, economy::Commodity, entity::astronomical::BodyIndex, knowledge::Observer,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Resources hidden within a body, unknown until surveyed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Deposit {
+    pub commodity: Commodity,
+    /// Units left to extract.
+    pub amount: f64,
+    /// Units before any were extracted.
+    pub initial: f64,
+    /// How hard the deposit is to detect, from 0 to 1.
+    /// Only surveys at least this accurate reveal it.
+    pub depth: f64,
+}
+
+/// Estimated amount of a deposit found by a survey.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub commodity: Commodity,
+    pub amount: f64,
+}
+
+/// What an observer has learned about the deposits of a body.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Survey {
+    pub body: BodyIndex,
+    pub surveyor: Observer,
+    /// Combined accuracy of all scans so far, from 0 to 1.
+    pub accuracy: f64,
+    pub estimates: Vec<Estimate>,
+}
+
+impl Survey {
+    /// Largest relative error of an estimate, at zero accuracy.
+    pub const MAX_ERROR: f64 = 0.8;
+
+    /// Create a new survey of the body which has not yet been scanned.
+    pub fn new(body: BodyIndex, surveyor: Observer) -> Self {
+        Survey {
+            body,
+            surveyor,
+            accuracy: 0.,
+            estimates: vec![],
+        }
+    }
+
+    /// Scan the deposits again using a scanner of the given quality,
+    /// improving the accuracy, refining estimates and revealing deeper deposits.
+    pub fn scan<R: Rng>(&mut self, deposits: &[Deposit], scanner: f64, rng: &mut R) {
+        self.accuracy = 1. - (1. - self.accuracy) * (1. - scanner.clamp(0., 1.));
+        let error = (1. - self.accuracy) * Survey::MAX_ERROR;
+        self.estimates = deposits
+            .iter()
+            .filter(|deposit| deposit.depth <= self.accuracy)
+            .map(|deposit| Estimate {
+                commodity: deposit.commodity,
+                amount: deposit.amount * (1. + rng.gen_range(-error, error + f64::EPSILON)),
+            })
+            .collect();
+    }
+
+    /// Estimated value of the found deposits at base prices.
+    pub fn value(&self) -> f64 {
+        self.estimates
+            .iter()
+            .map(|estimate| estimate.amount * estimate.commodity.base_price())
+            .sum()
+    }
+}
+
+/// Registered right to exploit the deposits of a body.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Claim {
+    pub body: BodyIndex,
+    pub owner: Observer,
+    /// Day the claim was registered.
+    pub registered: Date,
+}
+
+impl Claim {
+    /// Credits paid by the player to register a claim.
+    pub const FEE: f64 = 100.;
+
+    /// Fraction of the surveyed value paid when selling a claim, at full accuracy.
+    pub const SALE_FRACTION: f64 = 0.02;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::Seed;
+
+    #[test]
+    fn test_survey() {
+        let deposits = vec![
+            Deposit {
+                commodity: Commodity::Ore,
+                amount: 1000.,
+                initial: 1000.,
+                depth: 0.1,
+            },
+            Deposit {
+                commodity: Commodity::Tech,
+                amount: 50.,
+                initial: 50.,
+                depth: 0.7,
+            },
+        ];
+        let mut rng = Seed::new(42).rng();
+        let mut survey = Survey::new(BodyIndex::from(0), Observer::Player);
+
+        // A single scan only reveals the shallow deposit, roughly.
+        survey.scan(&deposits, 0.5, &mut rng);
+        assert_eq!(survey.accuracy, 0.5);
+        assert_eq!(survey.estimates.len(), 1);
+        assert!((survey.estimates[0].amount - 1000.).abs() <= 400.);
+
+        // Repeated scans reveal more and are more accurate.
+        survey.scan(&deposits, 0.5, &mut rng);
+        survey.scan(&deposits, 0.5, &mut rng);
+        assert_eq!(survey.accuracy, 0.875);
+        assert_eq!(survey.estimates.len(), 2);
+        assert!((survey.estimates[0].amount - 1000.).abs() <= 100.);
+        assert!(survey.value() > 0.);
+    }
+}
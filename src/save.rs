@@ -0,0 +1,340 @@
+use crate::{
+    calendar::Date,
+    config,
+    economy::{Market, Shipment},
+    entity::{
+        astronomical::{Body, BodyIndex},
+        ship::{Ship, ShipIndex},
+        EntityArray,
+    },
+    history::HistoryEvent,
+    knowledge::Chart,
+    mining::Operation,
+    missions::Mission,
+    prospecting::{Claim, Deposit, Survey},
+    world::World,
+};
+use failure::{format_err, Error};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Identifies the generated world a save applies to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Base {
+    /// Map seed the world was generated from.
+    pub seed: u32,
+    /// Hash of the generated world, catching changes to the generator or its
+    /// configuration which would make the same seed produce another world.
+    pub fingerprint: u64,
+    /// Number of history events recorded during generation.
+    pub events: usize,
+}
+
+impl Base {
+    /// Identify the world as generated from the given seed.
+    pub fn new(seed: u32, world: &World) -> Result<Self, Error> {
+        let mut hasher = Fingerprint(0xcbf2_9ce4_8422_2325);
+        serde_json::to_writer(&mut hasher, world)?;
+        Ok(Base {
+            seed,
+            fingerprint: hasher.0,
+            events: world.history.len(),
+        })
+    }
+}
+
+/// Writer hashing everything written using FNV-1a, which unlike the std
+/// hashers is guaranteed to be stable.
+struct Fingerprint(u64);
+
+impl Write for Fingerprint {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// State of a system changed by the live simulation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct SystemState {
+    population: u64,
+    market: Market,
+}
+
+/// The player and simulation state which changes after generation, applied
+/// on top of the world regenerated from the base.
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub base: Base,
+    pub day: Date,
+    systems: Vec<SystemState>,
+    /// Traffic of each lane.
+    volumes: Vec<f64>,
+    /// Deposits of the bodies which have been mined.
+    deposits: Vec<(BodyIndex, Vec<Deposit>)>,
+    ships: EntityArray<Ship>,
+    player: Option<ShipIndex>,
+    charts: Vec<Chart>,
+    surveys: Vec<Survey>,
+    claims: Vec<Claim>,
+    operations: Vec<Operation>,
+    missions: Vec<Mission>,
+    credits: f64,
+    shipments: Vec<Shipment>,
+    /// Events recorded after generation.
+    history: Vec<HistoryEvent>,
+}
+
+impl SaveGame {
+    /// Capture the state of the world generated from the given base.
+    pub fn capture(base: Base, world: &World) -> Self {
+        let mined = |(id, body): (usize, &Body)| {
+            if body
+                .deposits
+                .iter()
+                .any(|deposit| deposit.amount != deposit.initial)
+            {
+                Some((BodyIndex::from(id), body.deposits.clone()))
+            } else {
+                None
+            }
+        };
+        SaveGame {
+            base,
+            day: world.day,
+            systems: world
+                .systems
+                .iter()
+                .map(|system| SystemState {
+                    population: system.population,
+                    market: system.market.clone(),
+                })
+                .collect(),
+            volumes: world.lanes.iter().map(|lane| lane.volume).collect(),
+            deposits: world.bodies.iter().enumerate().filter_map(mined).collect(),
+            ships: world.ships.clone(),
+            player: world.player,
+            charts: world.charts.clone(),
+            surveys: world.surveys.clone(),
+            claims: world.claims.clone(),
+            operations: world.operations.clone(),
+            missions: world.missions.clone(),
+            credits: world.credits,
+            shipments: world.shipments.clone(),
+            history: world.history.iter().skip(base.events).cloned().collect(),
+        }
+    }
+
+    /// Restore the saved state onto a world generated from the given base.
+    /// Anything changed since generation is replaced, so the world may
+    /// already have been played on.
+    pub fn restore(self, base: &Base, world: &mut World) -> Result<(), Error> {
+        if self.base != *base {
+            return Err(format_err!(
+                "Saved from another world (seed {}) or another version of it",
+                self.base.seed
+            ));
+        }
+        if self.systems.len() != world.systems.len() || self.volumes.len() != world.lanes.len() {
+            return Err(format_err!("Save does not match the generated world"));
+        }
+
+        for (system, state) in world.systems.iter_mut().zip(self.systems) {
+            system.population = state.population;
+            system.market = state.market;
+        }
+        for (lane, volume) in world.lanes.iter_mut().zip(self.volumes) {
+            lane.volume = volume;
+        }
+        for deposit in world.bodies.iter_mut().flat_map(|body| &mut body.deposits) {
+            deposit.amount = deposit.initial;
+        }
+        for (body, deposits) in self.deposits {
+            world.bodies[body].deposits = deposits;
+        }
+        world.history.truncate(base.events);
+        world.history.extend(self.history);
+
+        world.day = self.day;
+        world.ships = self.ships;
+        world.player = self.player;
+        world.charts = self.charts;
+        world.surveys = self.surveys;
+        world.claims = self.claims;
+        world.operations = self.operations;
+        world.missions = self.missions;
+        world.credits = self.credits;
+        world.shipments = self.shipments;
+        Ok(())
+    }
+}
+
+/// Extension of save files.
+const EXTENSION: &str = "json";
+
+/// Path of the file holding the given slot.
+fn path(config: &config::Saves, name: &str) -> PathBuf {
+    Path::new(&config.directory)
+        .join(name)
+        .with_extension(EXTENSION)
+}
+
+/// Read the save in the given slot.
+pub fn read(config: &config::Saves, name: &str) -> Result<SaveGame, Error> {
+    let file = File::open(path(config, name))
+        .map_err(|e| format_err!("Failed to open save {}: {}", name, e))?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
+
+/// Named save slots, stored as one file each in the configured directory.
+pub struct Slots {
+    config: config::Saves,
+    base: Base,
+}
+
+impl Slots {
+    /// Create the slots for saving the world generated from the given base.
+    pub fn new(config: &config::Saves, base: Base) -> Self {
+        Slots {
+            config: config.clone(),
+            base,
+        }
+    }
+
+    /// Identity of the world saved in the slots.
+    pub fn base(&self) -> &Base {
+        &self.base
+    }
+
+    /// Save the world to the autosave slot.
+    pub fn autosave(&self, world: &World) -> Result<(), Error> {
+        self.save(&self.config.autosave_slot, world)
+    }
+
+    /// Names of all existing slots, most recently saved first.
+    pub fn list(&self) -> Vec<String> {
+        let entries = match fs::read_dir(&self.config.directory) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+        let mut slots: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+                let name = path.file_stem()?.to_str()?.to_string();
+                Some((modified, name))
+            })
+            .collect();
+        slots.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        slots.into_iter().map(|(_, name)| name).collect()
+    }
+
+    /// Save the world to the given slot.
+    /// The save is written to a temporary file which then replaces the slot,
+    /// so a crash while saving leaves any previous save intact.
+    pub fn save(&self, name: &str, world: &World) -> Result<(), Error> {
+        if name.is_empty() || name.contains(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+        {
+            return Err(format_err!(
+                "Slot names may only contain letters, digits, - and _"
+            ));
+        }
+        fs::create_dir_all(&self.config.directory)?;
+        let path = path(&self.config, name);
+        let temporary = path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&temporary)?);
+            serde_json::to_writer(&mut writer, &SaveGame::capture(self.base, world))?;
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        fs::rename(&temporary, &path)?;
+        info!("Saved {} to {}", world.day, path.display());
+        Ok(())
+    }
+
+    /// Load the given slot onto the world.
+    pub fn load(&self, name: &str, world: &mut World) -> Result<(), Error> {
+        read(&self.config, name)?.restore(&self.base, world)?;
+        info!("Loaded {} from slot {}", world.day, name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        economy::Commodity,
+        entity::astronomical::{BodyKind, System},
+        point::Point,
+    };
+
+    fn world() -> World {
+        let mut world = World::new();
+        let system = world.systems.insert(
+            System::builder()
+                .location(Point::origin())
+                .name("Sol")
+                .population(1_000u64)
+                .build(),
+        );
+        let body = world.bodies.insert(
+            Body::builder()
+                .kind(BodyKind::Rocky)
+                .orbit(1.)
+                .system(system)
+                .deposits(vec![Deposit {
+                    commodity: Commodity::Ore,
+                    amount: 100.,
+                    initial: 100.,
+                    depth: 0.5,
+                }])
+                .build(),
+        );
+        world.systems[system].bodies.push(body);
+        world
+    }
+
+    #[test]
+    fn test_restore() {
+        let mut world = world();
+        let base = Base::new(42, &world).unwrap();
+        assert_eq!(base, Base::new(42, &self::world()).unwrap());
+
+        let body = BodyIndex::from(0);
+        world.bodies[body].deposits[0].amount = 40.;
+        world.credits = 500.;
+        world.day = Date(10);
+        let json = serde_json::to_string(&SaveGame::capture(base, &world)).unwrap();
+
+        // Restoring onto a fresh world brings back the changes, and onto a
+        // world played further undoes what happened since.
+        for mut other in [self::world(), world] {
+            other.bodies[body].deposits[0].amount = 10.;
+            other.day = Date(20);
+            let save: SaveGame = serde_json::from_str(&json).unwrap();
+            save.restore(&base, &mut other).unwrap();
+            assert_eq!(other.bodies[body].deposits[0].amount, 40.);
+            assert_eq!(other.credits, 500.);
+            assert_eq!(other.day, Date(10));
+        }
+
+        let other = Base::new(7, &self::world()).unwrap();
+        let save: SaveGame = serde_json::from_str(&json).unwrap();
+        assert!(save.restore(&other, &mut self::world()).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use crate::point::Point;
+use std::collections::HashMap;
+
+/// Uniform grid for fast lookup of points within an area.
+/// Each point is stored along with an associated value, typically an entity index.
+pub struct SpatialIndex {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<(Point, usize)>>,
+    min_cell: (i64, i64),
+    max_cell: (i64, i64),
+}
+
+impl SpatialIndex {
+    /// Create a new empty index with the given cell size.
+    pub fn new(cell_size: f64) -> Self {
+        SpatialIndex {
+            cell_size,
+            cells: HashMap::new(),
+            min_cell: (0, 0),
+            max_cell: (0, 0),
+        }
+    }
+
+    /// Returns the grid cell containing the given point.
+    fn cell(&self, point: &Point) -> (i64, i64) {
+        (
+            (point.x / self.cell_size).floor() as i64,
+            (point.y / self.cell_size).floor() as i64,
+        )
+    }
+
+    /// Insert a point with its associated value.
+    pub fn insert(&mut self, point: Point, value: usize) {
+        let cell = self.cell(&point);
+        if self.cells.is_empty() {
+            self.min_cell = cell;
+            self.max_cell = cell;
+        } else {
+            self.min_cell = (self.min_cell.0.min(cell.0), self.min_cell.1.min(cell.1));
+            self.max_cell = (self.max_cell.0.max(cell.0), self.max_cell.1.max(cell.1));
+        }
+        self.cells.entry(cell).or_default().push((point, value));
+    }
+
+    /// Returns all points, with their values, within the given rectangle.
+    fn points(&self, min: Point, max: Point) -> Vec<&(Point, usize)> {
+        let (min_x, min_y) = self.cell(&min);
+        let (max_x, max_y) = self.cell(&max);
+
+        let mut points = vec![];
+        for x in min_x.max(self.min_cell.0)..=max_x.min(self.max_cell.0) {
+            for y in min_y.max(self.min_cell.1)..=max_y.min(self.max_cell.1) {
+                if let Some(cell) = self.cells.get(&(x, y)) {
+                    points.extend(cell.iter().filter(|(p, _)| {
+                        p.x >= min.x && p.x <= max.x && p.y >= min.y && p.y <= max.y
+                    }));
+                }
+            }
+        }
+        points
+    }
+
+    /// Returns the values of all points within the given rectangle.
+    pub fn query(&self, min: Point, max: Point) -> Vec<usize> {
+        self.points(min, max)
+            .into_iter()
+            .map(|(_, value)| *value)
+            .collect()
+    }
+
+    /// Returns the values of all points within the given distance, closest first.
+    pub fn within(&self, center: &Point, radius: f64) -> Vec<usize> {
+        let offset = Point::new(radius, radius);
+        let mut points: Vec<_> = self
+            .points(*center + offset * -1., *center + offset)
+            .into_iter()
+            .map(|(p, value)| (p.distance(center), *value))
+            .filter(|(distance, _)| *distance <= radius)
+            .collect();
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        points.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Returns the value of the point closest to the given location, if any.
+    pub fn nearest(&self, point: &Point) -> Option<usize> {
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        // Search rings of cells around the point, starting at the first ring
+        // which reaches any occupied cell.
+        let (cx, cy) = self.cell(point);
+        let dx = (self.min_cell.0 - cx).max(cx - self.max_cell.0).max(0);
+        let dy = (self.min_cell.1 - cy).max(cy - self.max_cell.1).max(0);
+        let mut ring = dx.max(dy);
+        let mut best: Option<(f64, usize)> = None;
+        loop {
+            let mut cells = vec![];
+            for i in -ring..=ring {
+                cells.push((cx + i, cy - ring));
+                cells.push((cx + i, cy + ring));
+            }
+            for i in (1 - ring)..ring {
+                cells.push((cx - ring, cy + i));
+                cells.push((cx + ring, cy + i));
+            }
+
+            for (p, value) in cells.iter().filter_map(|c| self.cells.get(c)).flatten() {
+                let distance = p.distance(point);
+                if best.is_none_or(|(d, _)| distance < d) {
+                    best = Some((distance, *value));
+                }
+            }
+
+            // Points in later rings are at least this far away.
+            if let Some((distance, _)) = best {
+                if distance <= ring as f64 * self.cell_size {
+                    break;
+                }
+            }
+
+            let covered = cx - ring <= self.min_cell.0
+                && cx + ring >= self.max_cell.0
+                && cy - ring <= self.min_cell.1
+                && cy + ring >= self.max_cell.1;
+            if covered {
+                break;
+            }
+            ring += 1;
+        }
+        best.map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SpatialIndex {
+        let mut index = SpatialIndex::new(10.);
+        index.insert(Point::new(-5., -5.), 0);
+        index.insert(Point::new(5., 5.), 1);
+        index.insert(Point::new(25., 5.), 2);
+        index.insert(Point::new(100., 100.), 3);
+        index
+    }
+
+    #[test]
+    fn test_query() {
+        let index = index();
+        let mut values = index.query(Point::new(-10., -10.), Point::new(30., 10.));
+        values.sort();
+        assert_eq!(values, vec![0, 1, 2]);
+
+        assert_eq!(
+            index.query(Point::new(0., 0.), Point::new(10., 10.)),
+            vec![1]
+        );
+        assert!(index
+            .query(Point::new(40., 40.), Point::new(50., 50.))
+            .is_empty());
+
+        // Huge areas, as seen when zoomed far out, only visit occupied cells.
+        let mut values = index.query(Point::new(-1e12, -1e12), Point::new(1e12, 1e12));
+        values.sort();
+        assert_eq!(values, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let index = index();
+        assert_eq!(index.within(&Point::new(4., 4.), 30.), vec![1, 0, 2]);
+        assert_eq!(index.nearest(&Point::new(-100., -100.)), Some(0));
+        assert_eq!(index.nearest(&Point::new(19., 5.)), Some(2));
+        assert_eq!(index.nearest(&Point::new(1000., 50.)), Some(3));
+        assert_eq!(SpatialIndex::new(1.).nearest(&Point::origin()), None);
+    }
+}
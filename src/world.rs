@@ -1,16 +1,485 @@
-use crate::entity::{astronomical::System, EntityArray};
+use crate::{
+    calendar::Date,
+    economy::{Commodity, Market, Shipment},
+    entity::{
+        astronomical::{Body, BodyIndex, Lane, System, SystemIndex},
+        faction::{Faction, FactionIndex, Relations},
+        ship::{Ship, ShipIndex},
+        EntityArray,
+    },
+    graph::LaneGraph,
+    history::{EventKind, HistoryEvent},
+    knowledge::{Chart, Knowledge, Observer},
+    mining::{Operation, OperationKind},
+    missions::{Mission, Objective, Status},
+    outfitting::Module,
+    prospecting::{Claim, Survey},
+};
+use failure::{format_err, Error};
+use log::debug;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 /// Holds the world state, i.e all entities.
 #[derive(Serialize, Deserialize)]
 pub struct World {
     pub systems: EntityArray<System>,
+    pub lanes: EntityArray<Lane>,
+    pub bodies: EntityArray<Body>,
+    pub factions: EntityArray<Faction>,
+    #[serde(default)]
+    pub relations: Relations,
+    #[serde(default)]
+    pub ships: EntityArray<Ship>,
+    /// Ship controlled by the player, once the game has started.
+    #[serde(default)]
+    pub player: Option<ShipIndex>,
+    /// What each observer knows about the galaxy.
+    #[serde(default)]
+    pub charts: Vec<Chart>,
+    /// Deposits found by each surveyor, at most one survey per body and surveyor.
+    #[serde(default)]
+    pub surveys: Vec<Survey>,
+    /// Registered claims, at most one per body.
+    #[serde(default)]
+    pub claims: Vec<Claim>,
+    /// Operations extracting the deposits of claimed bodies.
+    #[serde(default)]
+    pub operations: Vec<Operation>,
+    /// Missions offered on the boards of systems, along with those accepted
+    /// by the player and their outcomes.
+    #[serde(default)]
+    pub missions: Vec<Mission>,
+    /// Credits held by the player.
+    #[serde(default)]
+    pub credits: f64,
+    /// Notable events, oldest first.
+    #[serde(default)]
+    pub history: Vec<HistoryEvent>,
+    /// Goods in transit between markets.
+    #[serde(default)]
+    pub shipments: Vec<Shipment>,
+    /// Today, counted in days simulated since the world was generated.
+    pub day: Date,
 }
 
 impl World {
     pub fn new() -> Self {
         World {
             systems: EntityArray::new(),
+            lanes: EntityArray::new(),
+            bodies: EntityArray::new(),
+            factions: EntityArray::new(),
+            relations: Relations::default(),
+            ships: EntityArray::new(),
+            player: None,
+            charts: vec![],
+            surveys: vec![],
+            claims: vec![],
+            operations: vec![],
+            missions: vec![],
+            credits: 0.,
+            history: vec![],
+            shipments: vec![],
+            day: Date::default(),
         }
     }
+
+    /// Transfer the given system to the faction, removing it from any previous owner.
+    pub fn claim(&mut self, system: SystemIndex, faction: FactionIndex) {
+        if let Some(previous) = self.systems[system].faction {
+            self.factions[previous]
+                .systems
+                .retain(|&other| other != system);
+        }
+        self.systems[system].faction = Some(faction);
+        self.factions[faction].systems.push(system);
+    }
+
+    /// Record an event as happening today.
+    pub fn record(&mut self, kind: EventKind) {
+        debug!("{}: {:?}", self.day, kind);
+        self.history.push(HistoryEvent {
+            day: self.day,
+            kind,
+        });
+    }
+
+    /// Today's date.
+    pub fn date(&self) -> Date {
+        self.day
+    }
+
+    /// Move all ships along their routes for the given number of days.
+    /// Observing ships visit every system they arrive at, even when passing through.
+    pub fn travel(&mut self, days: f64) {
+        let (lanes, systems, bodies) = (&self.lanes, &self.systems, &self.bodies);
+        let charts = &mut self.charts;
+        for ship in self.ships.iter_mut() {
+            let arrivals = ship.advance(days, lanes, systems, |system| {
+                systems[system].refuels(bodies)
+            });
+            let chart = ship
+                .observer
+                .and_then(|observer| charts.iter_mut().find(|chart| chart.observer == observer));
+            if let Some(chart) = chart {
+                for system in arrivals {
+                    chart.visit(system, lanes);
+                    chart.sweep(&systems[system].location, ship.sensors, systems);
+                }
+            }
+        }
+    }
+
+    /// Let every observing ship update the chart of its observer.
+    pub fn explore(&mut self) {
+        let charts = &mut self.charts;
+        for ship in self.ships.iter() {
+            let chart = ship
+                .observer
+                .and_then(|observer| charts.iter_mut().find(|chart| chart.observer == observer));
+            if let Some(chart) = chart {
+                chart.observe(ship, &self.systems, &self.lanes);
+            }
+        }
+    }
+
+    /// Chart of the given observer, if it has one.
+    pub fn chart(&self, observer: Observer) -> Option<&Chart> {
+        self.charts.iter().find(|chart| chart.observer == observer)
+    }
+
+    /// What the player knows about the given system.
+    /// Without a player everything is known.
+    pub fn knowledge(&self, system: SystemIndex) -> Knowledge {
+        self.chart(Observer::Player)
+            .map_or(Knowledge::Visited, |chart| chart.get(system))
+    }
+
+    /// Market of the given system.
+    pub fn market(&self, system: SystemIndex) -> &Market {
+        &self.systems[system].market
+    }
+
+    /// How dangerous the system is to travel to, from 0 to 1.
+    pub fn danger(&self, system: SystemIndex) -> f64 {
+        let system = &self.systems[system];
+        match system.faction {
+            _ if system.contested => 1.,
+            None => 0.5,
+            Some(faction) => {
+                let at_war = (0..self.factions.len())
+                    .any(|other| self.relations.at_war(faction, FactionIndex::from(other)));
+                if at_war {
+                    0.4
+                } else {
+                    0.1
+                }
+            }
+        }
+    }
+
+    /// Accept the offered mission with the ship, which has to be stopped at
+    /// the system offering it.
+    pub fn accept_mission(&mut self, mission: usize, ship: ShipIndex) -> Result<(), Error> {
+        let offer = match self.missions.get(mission) {
+            Some(offer) if offer.status == Status::Offered => offer.clone(),
+            _ => return Err(format_err!("No such offer")),
+        };
+        let accepting = &self.ships[ship];
+        if accepting.travelling() || accepting.system != offer.origin {
+            return Err(format_err!(
+                "{} has to be stopped at the system to accept",
+                accepting.name
+            ));
+        }
+
+        match offer.objective {
+            Objective::Deliver {
+                commodity, units, ..
+            } => {
+                let accepting = &mut self.ships[ship];
+                if accepting.capacity - accepting.cargo.total() < units {
+                    return Err(format_err!("Needs {:.0} units of free cargo space", units));
+                }
+                accepting.cargo.add(commodity, units);
+            }
+            Objective::Escort { destination, .. } => {
+                let mut convoy = Ship::builder()
+                    .name("Convoy")
+                    .location(self.systems[offer.origin].location)
+                    .system(offer.origin)
+                    .speed(self.ships[ship].speed * Mission::CONVOY_SPEED)
+                    .fuel(f64::MAX)
+                    .build();
+                let route = LaneGraph::new(self)
+                    .plan(self, &convoy, destination, |_| true)
+                    .map_err(|e| format_err!("No route for the convoy: {}", e))?;
+                convoy.order(route, destination);
+                let convoy = self.ships.insert(convoy);
+                self.missions[mission].objective = Objective::Escort {
+                    destination,
+                    convoy: Some(convoy),
+                };
+            }
+            Objective::Survey { .. } | Objective::Find { .. } => {}
+        }
+        self.missions[mission].status = Status::Active;
+        Ok(())
+    }
+
+    /// Survey of the body made by the given observer, if any.
+    pub fn survey_of(&self, body: BodyIndex, surveyor: Observer) -> Option<&Survey> {
+        self.surveys
+            .iter()
+            .find(|survey| survey.body == body && survey.surveyor == surveyor)
+    }
+
+    /// Claim registered on the body, if any.
+    pub fn claim_on(&self, body: BodyIndex) -> Option<&Claim> {
+        self.claims.iter().find(|claim| claim.body == body)
+    }
+
+    /// Scan the body using the ship, which has to be stopped in the system of the body.
+    /// The results are added to the survey of the ship's observer.
+    pub fn survey<R: Rng>(
+        &mut self,
+        ship: ShipIndex,
+        body: BodyIndex,
+        rng: &mut R,
+    ) -> Result<&Survey, Error> {
+        let ship = &self.ships[ship];
+        let surveyor = ship
+            .observer
+            .ok_or_else(|| format_err!("{} has no one to report to", ship.name))?;
+        if ship.travelling() || ship.system != self.bodies[body].system {
+            return Err(format_err!(
+                "{} has to be stopped in the system to survey",
+                ship.name
+            ));
+        }
+        if ship.scanner <= 0. {
+            return Err(format_err!("{} has no scanner", ship.name));
+        }
+
+        let index = match self
+            .surveys
+            .iter()
+            .position(|survey| survey.body == body && survey.surveyor == surveyor)
+        {
+            Some(index) => index,
+            None => {
+                self.surveys.push(Survey::new(body, surveyor));
+                self.surveys.len() - 1
+            }
+        };
+        let survey = &mut self.surveys[index];
+        survey.scan(&self.bodies[body].deposits, ship.scanner, rng);
+        Ok(survey)
+    }
+
+    /// Register a claim on the body for the owner, who has to have found deposits there.
+    /// The player pays a registration fee.
+    pub fn register_claim(&mut self, body: BodyIndex, owner: Observer) -> Result<(), Error> {
+        if let Some(claim) = self.claim_on(body) {
+            return Err(if claim.owner == owner {
+                format_err!("Already claimed")
+            } else {
+                format_err!("Already claimed by someone else")
+            });
+        }
+        if self
+            .survey_of(body, owner)
+            .is_none_or(|survey| survey.estimates.is_empty())
+        {
+            return Err(format_err!("No deposits found to claim"));
+        }
+        if owner == Observer::Player {
+            if self.credits < Claim::FEE {
+                return Err(format_err!(
+                    "Registering a claim costs {} credits",
+                    Claim::FEE
+                ));
+            }
+            self.credits -= Claim::FEE;
+        }
+
+        self.claims.push(Claim {
+            body,
+            owner,
+            registered: self.day,
+        });
+        Ok(())
+    }
+
+    /// Operation extracting the deposits of the body, if any.
+    pub fn operation_on(&self, body: BodyIndex) -> Option<&Operation> {
+        self.operations
+            .iter()
+            .find(|operation| operation.body == body)
+    }
+
+    /// Set up an operation of the player on the body, which the player has
+    /// to have claimed and the ship to be stopped in the system of.
+    pub fn build_operation(
+        &mut self,
+        ship: ShipIndex,
+        body: BodyIndex,
+    ) -> Result<OperationKind, Error> {
+        let ship = &self.ships[ship];
+        if ship.travelling() || ship.system != self.bodies[body].system {
+            return Err(format_err!(
+                "{} has to be stopped in the system to build",
+                ship.name
+            ));
+        }
+        if self
+            .claim_on(body)
+            .is_none_or(|claim| claim.owner != Observer::Player)
+        {
+            return Err(format_err!("Only claimed bodies can be exploited"));
+        }
+        if self.operation_on(body).is_some() {
+            return Err(format_err!("Already exploited"));
+        }
+        let kind = OperationKind::for_body(self.bodies[body].kind);
+        if self.credits < kind.cost() {
+            return Err(format_err!("{} costs {} credits", kind.name(), kind.cost()));
+        }
+
+        self.credits -= kind.cost();
+        self.operations.push(Operation {
+            kind,
+            body,
+            owner: Observer::Player,
+            built: self.day,
+            stock: Default::default(),
+        });
+        Ok(kind)
+    }
+
+    /// Units of the commodity the player has to deliver for active missions.
+    pub fn reserved(&self, commodity: Commodity) -> f64 {
+        self.missions
+            .iter()
+            .filter(|mission| mission.status == Status::Active)
+            .map(|mission| match mission.objective {
+                Objective::Deliver {
+                    commodity: other,
+                    units,
+                    ..
+                } if other == commodity => units,
+                _ => 0.,
+            })
+            .sum()
+    }
+
+    /// Load the output of the player's operations in the system the ship is
+    /// stopped at, then sell all cargo not reserved for missions if the system
+    /// has a market. Returns the units loaded and the credits earned.
+    pub fn deliver(&mut self, ship: ShipIndex) -> Result<(f64, f64), Error> {
+        let reserved: Vec<f64> = Commodity::ALL
+            .iter()
+            .map(|&commodity| self.reserved(commodity))
+            .collect();
+        let ship = &mut self.ships[ship];
+        if ship.travelling() {
+            return Err(format_err!(
+                "{} has to be stopped to load or sell",
+                ship.name
+            ));
+        }
+
+        let mut loaded = 0.;
+        let (bodies, here) = (&self.bodies, ship.system);
+        for operation in self.operations.iter_mut().filter(|operation| {
+            operation.owner == Observer::Player && bodies[operation.body].system == here
+        }) {
+            let goods: Vec<_> = operation.stock.goods().collect();
+            for (commodity, units) in goods {
+                let space = (ship.capacity - ship.cargo.total()).max(0.);
+                let units = operation.stock.take(commodity, units.min(space));
+                ship.cargo.add(commodity, units);
+                loaded += units;
+            }
+        }
+
+        let mut earned = 0.;
+        let system = &mut self.systems[here];
+        if system.population > 0 {
+            for (&commodity, &reserved) in Commodity::ALL.iter().zip(&reserved) {
+                let units = (ship.cargo.get(commodity) - reserved).max(0.);
+                earned += system
+                    .market
+                    .sell(commodity, ship.cargo.take(commodity, units));
+            }
+        }
+
+        if loaded <= 0. && earned <= 0. {
+            return Err(format_err!("Nothing to load or sell here"));
+        }
+        self.credits += earned;
+        Ok((loaded, earned))
+    }
+
+    /// Sell the player's claim on the body to the faction owning its system,
+    /// priced by the surveyed value and accuracy. Returns the credits earned.
+    pub fn sell_claim(&mut self, body: BodyIndex) -> Result<f64, Error> {
+        let index = self
+            .claims
+            .iter()
+            .position(|claim| claim.body == body && claim.owner == Observer::Player)
+            .ok_or_else(|| format_err!("No claim of yours to sell"))?;
+        if self.operation_on(body).is_some() {
+            return Err(format_err!("Claims being exploited can not be sold"));
+        }
+        let buyer = self.systems[self.bodies[body].system]
+            .faction
+            .ok_or_else(|| format_err!("No one to sell to in unclaimed space"))?;
+        let price = self
+            .survey_of(body, Observer::Player)
+            .map_or(0., |survey| {
+                survey.value() * survey.accuracy * Claim::SALE_FRACTION
+            })
+            .round();
+
+        self.claims[index].owner = Observer::Faction(buyer);
+        self.credits += price;
+        Ok(price)
+    }
+
+    /// Replace the module in the given slot of the player's ship, buying the
+    /// new module and selling the old one at the station of the system the
+    /// ship is stopped at. Returns the credits paid, negative if earned.
+    pub fn refit(
+        &mut self,
+        ship: ShipIndex,
+        slot: usize,
+        module: Option<Module>,
+    ) -> Result<f64, Error> {
+        let ship = &mut self.ships[ship];
+        if ship.travelling() || self.systems[ship.system].population == 0 {
+            return Err(format_err!(
+                "{} has to be stopped at an inhabited system to refit",
+                ship.name
+            ));
+        }
+        let outfit = ship
+            .outfit
+            .as_ref()
+            .ok_or_else(|| format_err!("{} can not be refitted", ship.name))?;
+        let refitted = outfit.refitted(slot, module)?;
+        if refitted.stats().capacity < ship.cargo.total() {
+            return Err(format_err!("Cargo does not fit the smaller hold"));
+        }
+
+        let resale = outfit.modules[slot].map_or(0., |old| old.price() * Module::RESALE_FRACTION);
+        let cost = module.map_or(0., Module::price) - resale;
+        if cost > self.credits {
+            return Err(format_err!("Refitting costs {:.0} credits", cost));
+        }
+        self.credits -= cost;
+        ship.refit(refitted);
+        Ok(cost)
+    }
 }